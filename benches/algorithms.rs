@@ -0,0 +1,146 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{Rng, SeedableRng};
+use rand::distributions::uniform::Uniform;
+use rand::rngs::StdRng;
+
+use max_prod::{max_prod_auto_int, max_prod_brute_force, max_prod_brute_force_unchecked, max_prod_brute_force_improved, max_prod_brute_force_improved_with_value, max_prod_fast_int, max_prod_fast_real, max_prod_fast_real_f64, max_prod_fast_real_log, prod};
+
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+// the brute forces are O(n^3) and O(n^2), larger sizes would take forever
+const MAX_SIZE_BRUTE_FORCE: usize = 100;
+const MAX_SIZE_BRUTE_FORCE_IMPROVED: usize = 1_000;
+
+fn int_array(n: usize) -> Vec<u128> {
+    // one in four elements is a zero, which keeps the runs short enough not to overflow
+    StdRng::seed_from_u64(42).sample_iter(Uniform::new_inclusive(0, 3)).take(n).collect()
+}
+
+fn real_array(n: usize) -> Vec<f64> {
+    StdRng::seed_from_u64(42).sample_iter(Uniform::new(0.0, 2.0)).take(n).collect()
+}
+
+fn bench_int(c: &mut Criterion) {
+    let mut group = c.benchmark_group("int");
+
+    for n in SIZES {
+        let arr = int_array(n);
+        group.throughput(Throughput::Elements(n as u64));
+
+        if n <= MAX_SIZE_BRUTE_FORCE {
+            group.bench_with_input(BenchmarkId::new("brute_force", n), &arr, |b, arr| b.iter(|| max_prod_brute_force(black_box(arr))));
+            // SAFETY: the array isn't empty
+            group.bench_with_input(BenchmarkId::new("brute_force_unchecked", n), &arr, |b, arr| b.iter(|| unsafe { max_prod_brute_force_unchecked(black_box(arr)) }));
+        }
+        if n <= MAX_SIZE_BRUTE_FORCE_IMPROVED {
+            group.bench_with_input(BenchmarkId::new("brute_force_improved", n), &arr, |b, arr| b.iter(|| max_prod_brute_force_improved(black_box(arr))));
+        }
+        group.bench_with_input(BenchmarkId::new("fast_int", n), &arr, |b, arr| b.iter(|| max_prod_fast_int(black_box(arr))));
+    }
+
+    group.finish();
+}
+
+fn bench_real(c: &mut Criterion) {
+    let mut group = c.benchmark_group("real");
+
+    for n in SIZES {
+        let arr = real_array(n);
+        group.throughput(Throughput::Elements(n as u64));
+
+        if n <= MAX_SIZE_BRUTE_FORCE {
+            group.bench_with_input(BenchmarkId::new("brute_force", n), &arr, |b, arr| b.iter(|| max_prod_brute_force(black_box(arr))));
+        }
+        if n <= MAX_SIZE_BRUTE_FORCE_IMPROVED {
+            group.bench_with_input(BenchmarkId::new("brute_force_improved", n), &arr, |b, arr| b.iter(|| max_prod_brute_force_improved(black_box(arr))));
+        }
+        group.bench_with_input(BenchmarkId::new("fast_real", n), &arr, |b, arr| b.iter(|| max_prod_fast_real(black_box(arr))));
+        group.bench_with_input(BenchmarkId::new("fast_real_f64", n), &arr, |b, arr| b.iter(|| max_prod_fast_real_f64(black_box(arr))));
+    }
+
+    group.finish();
+}
+
+fn bench_prod(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prod");
+
+    // many short products, where the cost of a call would show. `prod` is generic and
+    // instantiated in the caller's crate, so it can be inlined already, with
+    // `#[inline(always)]` on it these and the int benches didn't change beyond noise.
+    for len in [1, 4, 16] {
+        let arr = real_array(10_000);
+        group.throughput(Throughput::Elements(arr.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("windows", len), &arr, |b, arr| {
+            b.iter(|| (0..=arr.len() - len).map(|i| prod(black_box(arr), i, i + len - 1)).sum::<f64>())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_auto(c: &mut Criterion) {
+    let mut group = c.benchmark_group("auto");
+
+    // tiny arrays, where the brute force might beat the fast algorithm, it didn't at any
+    // length so max_prod_auto_int never picks it
+    for n in [1, 2, 3, 4, 8, 16, 32] {
+        let arr: Vec<u32> = int_array(n).into_iter().map(|x| x as u32).collect();
+        group.throughput(Throughput::Elements(n as u64));
+
+        group.bench_with_input(BenchmarkId::new("brute_force_improved", n), &arr, |b, arr| b.iter(|| max_prod_brute_force_improved(black_box(arr))));
+        group.bench_with_input(BenchmarkId::new("fast_int", n), &arr, |b, arr| b.iter(|| max_prod_fast_int(black_box(arr))));
+        group.bench_with_input(BenchmarkId::new("auto_int", n), &arr, |b, arr| b.iter(|| max_prod_auto_int(black_box(arr))));
+    }
+
+    // arrays that are almost all zeros, these picked the density at which
+    // max_prod_auto_int skips blocks of zeros
+    for zeros in [0.9, 0.98, 0.99, 0.999] {
+        let n = 1 << 16;
+        let mut rng = StdRng::seed_from_u64(42);
+        let arr: Vec<u32> = (0..n).map(|_| if rng.gen_bool(zeros) { 0 } else { rng.gen_range(1..4) }).collect();
+        group.throughput(Throughput::Elements(n as u64));
+
+        let id = format!("{n}/{zeros}");
+        group.bench_with_input(BenchmarkId::new("sparse_brute_force_runs", &id), &arr, |b, arr| b.iter(|| brute_force_runs(black_box(arr))));
+        group.bench_with_input(BenchmarkId::new("sparse_fast_int", &id), &arr, |b, arr| b.iter(|| max_prod_fast_int(black_box(arr))));
+        group.bench_with_input(BenchmarkId::new("sparse_auto_int", &id), &arr, |b, arr| b.iter(|| max_prod_auto_int(black_box(arr))));
+    }
+
+    group.finish();
+}
+
+/// The brute force on every run between two zeros, which the runs of sparse arrays keep
+/// tiny.
+fn brute_force_runs(arr: &[u32]) -> (usize, usize) {
+    let mut best = (0, 0, 0);
+    let mut start = 0;
+
+    for run in arr.split(|&x| x == 0) {
+        let (p, i, j) = max_prod_brute_force_improved_with_value(run);
+        if p > best.0 {
+            best = (p, start + i, start + j);
+        }
+        start += run.len() + 1;
+    }
+
+    (best.1, best.2)
+}
+
+fn bench_log(c: &mut Criterion) {
+    let mut group = c.benchmark_group("log");
+
+    let arr: Vec<f32> = real_array(100_000).into_iter().map(|x| x as f32).collect();
+    group.throughput(Throughput::Elements(arr.len() as u64));
+
+    group.bench_with_input(BenchmarkId::new("fast_real_log", arr.len()), &arr, |b, arr| b.iter(|| max_prod_fast_real_log(black_box(arr))));
+    #[cfg(feature = "simd")]
+    group.bench_with_input(BenchmarkId::new("fast_real_simd", arr.len()), &arr, |b, arr| b.iter(|| max_prod::max_prod_fast_real_simd(black_box(arr))));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_int, bench_real, bench_prod, bench_auto, bench_log);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+//! Checks that the integer algorithms build without `std` and without an allocator.
+//!
+//! Build with `cargo build` from this directory.
+
+#![no_std]
+
+use max_prod::{max_prod_brute_force_improved, max_prod_fast_int, MaxProdIntTracker};
+
+pub fn best_range(arr: &[u32]) -> (usize, usize) {
+    max_prod_fast_int(arr)
+}
+
+pub fn best_range_brute_force(arr: &[u32]) -> (usize, usize) {
+    max_prod_brute_force_improved(arr)
+}
+
+pub fn best_range_streaming(arr: &[u32]) -> (usize, usize) {
+    let mut tracker = MaxProdIntTracker::new();
+    for &x in arr {
+        tracker.push(x);
+    }
+    tracker.best()
+}
@@ -0,0 +1,5 @@
+//! Exports [`max_prod_fast_real_js`] to JavaScript.
+//!
+//! Build with `wasm-pack build --target web` from this directory and open `index.html`.
+
+pub use max_prod::max_prod_fast_real_js;
@@ -0,0 +1,29 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use max_prod::max_prod_fast_real_with_value;
+
+// Every 8 bytes are one f64, so NaNs, infinities, zeros, subnormals and exact ones all
+// show up. Negative finite numbers are flipped, the algorithm is only meant for
+// non-negative reals, -inf stays as it is.
+fuzz_target!(|data: &[u8]| {
+    let arr: Vec<f64> = data
+        .chunks_exact(8)
+        .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+        .map(|x| if x.is_finite() && x < 0.0 { -x } else { x })
+        .collect();
+
+    let (p, i, j) = max_prod_fast_real_with_value(&arr);
+
+    if arr.is_empty() {
+        assert_eq!((i, j), (0, 0));
+        return;
+    }
+
+    assert!(i <= j && j < arr.len());
+
+    // NaN and -inf are treated like zeros, so they are skipped
+    for &x in arr.iter().filter(|&&x| x >= 0.0) {
+        assert!(p >= x, "{x} is larger than the product {p} of [{i} .. {j}] in {arr:?}");
+    }
+});
@@ -0,0 +1,128 @@
+use crate::int::MaxProdInt;
+use crate::max_prod::{best_or_default, max_prod_fast_int_with_value};
+
+/// Keeps the best range over many arrays, see [`consider`](Self::consider).
+///
+/// Every array is placed at a caller-supplied `offset` into a virtual concatenation and the
+/// ranges are reported in those virtual indices. The arrays are searched independently, so
+/// a range never spans two arrays even if they are adjacent. Of two ranges with the same
+/// product the one with the smaller start and then the smaller end wins, so the order in
+/// which the arrays are considered doesn't matter.
+///
+/// ```
+/// use max_prod::MaxProdAccumulator;
+///
+/// let mut acc = MaxProdAccumulator::new();
+/// acc.consider(&[2u32, 0, 3], 0);
+/// acc.consider(&[4u32, 2], 3);
+/// assert_eq!(acc.best(), (3, 4));
+/// assert_eq!(acc.best_prod(), 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxProdAccumulator<T> {
+    best: Option<(T, usize, usize)>,
+}
+
+impl<T: MaxProdInt> MaxProdAccumulator<T> {
+    pub fn new() -> Self {
+        MaxProdAccumulator { best: None }
+    }
+
+    /// Searches `arr` with [`max_prod_fast_int`](crate::max_prod_fast_int) and keeps its best
+    /// range, shifted by `offset`, if it beats the best one so far.
+    pub fn consider(&mut self, arr: &[T], offset: usize) {
+        if arr.is_empty() {
+            return;
+        }
+
+        let (p, i, j) = max_prod_fast_int_with_value(arr);
+        let (i, j) = (i + offset, j + offset);
+
+        // not `max_prod::consider`, an array considered later can come first and then wins a tie
+        if p > T::zero() && self.best.is_none_or(|(b, bi, bj)| p > b || (p == b && (i, j) < (bi, bj))) {
+            self.best = Some((p, i, j));
+        }
+    }
+
+    /// The best range of all arrays considered so far, `(0, 0)` if none had a non-zero element.
+    pub fn best(&self) -> (usize, usize) {
+        let (_, i, j) = best_or_default(self.best).unwrap();
+        (i, j)
+    }
+
+    /// The product of [`best`](Self::best).
+    pub fn best_prod(&self) -> T {
+        best_or_default(self.best).unwrap().0
+    }
+}
+
+impl<T: MaxProdInt> Default for MaxProdAccumulator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_accumulator_three_arrays() {
+        let mut acc = MaxProdAccumulator::new();
+        assert_eq!(acc.best(), (0, 0));
+
+        acc.consider(&[2u32, 3, 0, 1], 0);
+        assert_eq!((acc.best(), acc.best_prod()), ((0, 1), 6));
+
+        acc.consider(&[0u32, 4, 5], 100);
+        assert_eq!((acc.best(), acc.best_prod()), ((101, 102), 20));
+
+        acc.consider(&[7u32, 0, 2], 50);
+        assert_eq!((acc.best(), acc.best_prod()), ((101, 102), 20));
+    }
+
+    #[test]
+    fn test_accumulator_ties() {
+        // the smaller offset wins regardless of the order
+        let mut acc = MaxProdAccumulator::default();
+        acc.consider(&[6u32], 10);
+        acc.consider(&[2u32, 3], 4);
+        acc.consider(&[0u32, 6], 20);
+        assert_eq!(acc.best(), (4, 5));
+
+        let mut acc = MaxProdAccumulator::new();
+        acc.consider(&[0u32, 0], 5);
+        acc.consider(&[], 1);
+        assert_eq!((acc.best(), acc.best_prod()), ((0, 0), 0));
+    }
+
+    #[test]
+    fn test_random_accumulator() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            // arrays separated by zeros, so the concatenation has the same best range
+            let mut concat = Vec::new();
+            let mut acc = MaxProdAccumulator::new();
+
+            for _ in 0..rng.gen_range(1..5) {
+                let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(10).collect();
+                concat.push(0);
+                acc.consider(&a, concat.len());
+                concat.extend(a);
+            }
+
+            let (p, i, j) = max_prod_fast_int_with_value(&concat);
+            if p != 0 {
+                assert_eq!((acc.best_prod(), acc.best()), (p, (i, j)), "{concat:?}");
+            } else {
+                assert_eq!(acc.best_prod(), 0);
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use num::traits::Num;
+#[cfg(feature = "alloc")]
+use num::traits::real::Real;
+
+use crate::int::MaxProdInt;
+use crate::max_prod::{max_prod_brute_force, max_prod_brute_force_improved, max_prod_fast_int};
+#[cfg(feature = "alloc")]
+use crate::real::max_prod_fast_real;
+
+/// An algorithm that finds the range with the maximum product, implemented by the marker
+/// types below so that algorithms can be picked at runtime, e.g. as
+/// `Box<dyn MaxProdAlgorithm<u128>>`.
+pub trait MaxProdAlgorithm<T> {
+    fn run(&self, arr: &[T]) -> (usize, usize);
+
+    fn name(&self) -> &'static str;
+}
+
+/// [`max_prod_brute_force`](crate::max_prod_brute_force)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BruteForce;
+
+/// [`max_prod_brute_force_improved`](crate::max_prod_brute_force_improved)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BruteForceImproved;
+
+/// [`max_prod_fast_int`](crate::max_prod_fast_int)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FastInt;
+
+/// [`max_prod_fast_real`](crate::max_prod_fast_real)
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FastReal;
+
+impl<T: Num + Copy + PartialOrd> MaxProdAlgorithm<T> for BruteForce {
+    fn run(&self, arr: &[T]) -> (usize, usize) {
+        max_prod_brute_force(arr)
+    }
+
+    fn name(&self) -> &'static str {
+        "brute force"
+    }
+}
+
+impl<T: Num + Copy + PartialOrd> MaxProdAlgorithm<T> for BruteForceImproved {
+    fn run(&self, arr: &[T]) -> (usize, usize) {
+        max_prod_brute_force_improved(arr)
+    }
+
+    fn name(&self) -> &'static str {
+        "brute force improved"
+    }
+}
+
+impl<T: MaxProdInt> MaxProdAlgorithm<T> for FastInt {
+    fn run(&self, arr: &[T]) -> (usize, usize) {
+        max_prod_fast_int(arr)
+    }
+
+    fn name(&self) -> &'static str {
+        "fast int"
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Real + Copy> MaxProdAlgorithm<T> for FastReal {
+    fn run(&self, arr: &[T]) -> (usize, usize) {
+        max_prod_fast_real(arr)
+    }
+
+    fn name(&self) -> &'static str {
+        "fast real"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_all_int_algorithms() {
+        let mut rng = test_rng();
+        let algorithms: Vec<Box<dyn MaxProdAlgorithm<u128>>> = vec![Box::new(BruteForce), Box::new(BruteForceImproved), Box::new(FastInt)];
+
+        for _ in 0..200 {
+            let a: Vec<u128> = Uniform::new_inclusive(0, 5).sample_iter(&mut rng).take(50).collect();
+            let expected = algorithms[0].run(&a);
+
+            for algorithm in &algorithms {
+                assert_eq!(algorithm.run(&a), expected, "{} disagrees", algorithm.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_real_algorithms() {
+        let mut rng = test_rng();
+        let algorithms: Vec<Box<dyn MaxProdAlgorithm<f64>>> = vec![Box::new(BruteForce), Box::new(BruteForceImproved), Box::new(FastReal)];
+
+        for _ in 0..200 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(50).collect();
+            let expected = algorithms[0].run(&a);
+
+            for algorithm in &algorithms {
+                assert_eq!(algorithm.run(&a), expected, "{} disagrees", algorithm.name());
+            }
+        }
+    }
+}
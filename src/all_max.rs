@@ -0,0 +1,101 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::int::MaxProdInt;
+use crate::max_prod::prod;
+use crate::runs::positive_runs_int;
+
+/// Every maximal range whose product equals the maximum product, sorted by start.
+///
+/// A range is maximal if extending it by one element on either side changes its
+/// product. Inside a run of non-zero elements every range with the product of the whole
+/// run only leaves out ones, so there is at most one maximal range per run and the ranges
+/// never overlap. The range returned by [`max_prod_fast_int`](crate::max_prod_fast_int) is
+/// contained in the first one. If `arr` only contains zeros the whole array is returned,
+/// if it is empty nothing is.
+///
+/// ```
+/// use max_prod::all_max_prod_fast_int;
+///
+/// assert_eq!(all_max_prod_fast_int([2u32, 3, 0, 1, 6, 0, 5]), vec![(0, 1), (3, 4)]);
+/// ```
+#[must_use]
+pub fn all_max_prod_fast_int<T: MaxProdInt>(arr: impl AsRef<[T]>) -> Vec<(usize, usize)> {
+    let arr = arr.as_ref();
+    let n = arr.len();
+    let mut max_prod = T::zero();
+    let mut all = Vec::new();
+
+    for (start, end) in positive_runs_int(arr) {
+        let run_prod = prod(arr, start, end);
+
+        if run_prod > max_prod {
+            max_prod = run_prod;
+            all.clear();
+        }
+        if run_prod == max_prod {
+            all.push((start, end));
+        }
+    }
+
+    if max_prod == T::zero() && n > 0 {
+        return vec![(0, n - 1)];
+    }
+
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::{max_prod_brute_force_with_value, max_prod_fast_int};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_all_max_ties() {
+        assert_eq!(all_max_prod_fast_int([2u32, 1, 1, 2]), vec![(0, 3)]);
+        assert_eq!(all_max_prod_fast_int([2u32, 1, 0, 1, 2]), vec![(0, 1), (3, 4)]);
+        assert_eq!(all_max_prod_fast_int([1u32, 4, 0, 2, 2, 0, 3]), vec![(0, 1), (3, 4)]);
+        assert_eq!(all_max_prod_fast_int([1u32, 0, 1, 1, 0, 1]), vec![(0, 0), (2, 3), (5, 5)]);
+        assert_eq!(all_max_prod_fast_int([4u32, 0, 5]), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_all_max_zeros() {
+        assert_eq!(all_max_prod_fast_int([0u32, 0, 0]), vec![(0, 2)]);
+        assert_eq!(all_max_prod_fast_int([0u32]), vec![(0, 0)]);
+        assert_eq!(all_max_prod_fast_int::<u32>([]), vec![]);
+    }
+
+    #[test]
+    fn test_random_all_max() {
+        let mut rng = test_rng();
+
+        for _ in 0..200 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 3).sample_iter(&mut rng).take(15).collect();
+            let (p, _, _) = max_prod_brute_force_with_value(&a);
+            let all = all_max_prod_fast_int(&a);
+
+            let (i, j) = max_prod_fast_int(&a);
+            assert!(all[0].0 <= i && j <= all[0].1, "{a:?}");
+
+            for &(i, j) in &all {
+                assert_eq!(prod(&a, i, j), p, "{a:?}");
+            }
+
+            // every tied range lies inside exactly one of the reported ones
+            for i in 0..a.len() {
+                for j in i..a.len() {
+                    if prod(&a, i, j) == p {
+                        let containing = all.iter().filter(|&&(s, e)| s <= i && j <= e).count();
+                        assert_eq!(containing, 1, "{a:?} ({i}, {j})");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::int::MaxProdInt;
+use crate::max_prod::{best_or_default, consider, max_prod_fast_int};
+
+/// Arrays shorter than this always go to `max_prod_fast_int`, for them the scan would be a
+/// noticeable part of the cost.
+const SPARSE_MIN_LEN: usize = 1 << 12;
+
+/// Number of evenly spaced elements the scan looks at.
+const SAMPLE_LEN: usize = 256;
+
+/// If at most this many sampled elements aren't zero, about 99% zeros, the array goes to
+/// the version that skips blocks of zeros.
+///
+/// In the `auto` benchmarks with 65536 `u32`s `max_prod_auto_int` is 1.4x faster than
+/// `max_prod_fast_int` at 99% zeros and 3.5x at 99.9%. Skipping blocks breaks even at about
+/// 98.5% and is 2x slower at 90%. The brute force on every run between two zeros was 3x to
+/// 5x slower than `max_prod_fast_int` at every density, and on the whole array it was
+/// slower at every length, 1.1x already for a single element, so it is never picked.
+const SPARSE_MAX_NON_ZERO: usize = 2;
+
+/// Length of the blocks of zeros that are skipped at once.
+const ZERO_BLOCK: usize = 32;
+
+/// [`max_prod_fast_int`](crate::max_prod_fast_int) with a quick scan first, arrays that are
+/// almost all zeros are searched by skipping whole blocks of zeros.
+///
+/// The scan only samples a few elements, so its cost doesn't grow with the length of
+/// `arr`. Both versions find the same range with ties included, so the result is always
+/// the one of `max_prod_fast_int`.
+///
+/// ```
+/// use max_prod::max_prod_auto_int;
+///
+/// assert_eq!(max_prod_auto_int([2u32, 0, 3, 4, 0, 5]), (2, 3));
+///
+/// let mut sparse = vec![0u32; 10_000];
+/// sparse[7000] = 3;
+/// sparse[7001] = 2;
+/// assert_eq!(max_prod_auto_int(&sparse), (7000, 7001));
+/// ```
+#[must_use]
+pub fn max_prod_auto_int<T: MaxProdInt>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let arr = arr.as_ref();
+
+    if mostly_zeros(arr) {
+        max_prod_fast_int_sparse(arr)
+    } else {
+        max_prod_fast_int(arr)
+    }
+}
+
+fn mostly_zeros<T: MaxProdInt>(arr: &[T]) -> bool {
+    if arr.len() < SPARSE_MIN_LEN {
+        return false;
+    }
+
+    let step = arr.len() / SAMPLE_LEN;
+    let non_zero = arr.iter().step_by(step).take(SAMPLE_LEN).filter(|&&x| x != T::zero()).count();
+    non_zero <= SPARSE_MAX_NON_ZERO
+}
+
+/// `max_prod_fast_int` that tests whole blocks of zeros at once, which compiles to a few
+/// vector comparisons instead of a branch per element.
+fn max_prod_fast_int_sparse<T: MaxProdInt>(arr: &[T]) -> (usize, usize) {
+    let mut best = None;
+    let n = arr.len();
+    let mut i = 0;
+
+    while i < n {
+        if i + ZERO_BLOCK <= n && arr[i..i + ZERO_BLOCK].iter().fold(true, |zero, &x| zero & (x == T::zero())) {
+            i += ZERO_BLOCK;
+            continue;
+        }
+
+        if arr[i] == T::zero() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut current_prod = T::one();
+
+        while i < n && arr[i] != T::zero() {
+            current_prod = current_prod * arr[i];
+            consider(&mut best, current_prod, start, i);
+            i += 1;
+        }
+    }
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    (i, j)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_auto_sizes() {
+        let mut rng = test_rng();
+
+        for n in 0..40 {
+            for _ in 0..50 {
+                let hi = if rng.gen_bool(0.5) { 1 } else { 4 };
+                let a: Vec<u64> = Uniform::new_inclusive(0, hi).sample_iter(&mut rng).take(n).collect();
+                let expected = max_prod_fast_int(&a);
+
+                assert_eq!(max_prod_auto_int(&a), expected, "a = {:?}", a);
+                // every length through the version for sparse arrays
+                assert_eq!(max_prod_fast_int_sparse(&a), expected, "a = {:?}", a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_auto_sparse() {
+        let mut rng = test_rng();
+
+        for _ in 0..200 {
+            let n = rng.gen_range(0..3 * SPARSE_MIN_LEN);
+            let density = [0.5, 0.9, 0.99, 0.999, 1.0][rng.gen_range(0..5)];
+            let a: Vec<u64> = (0..n).map(|_| if rng.gen_bool(density) { 0 } else { rng.gen_range(1..4) }).collect();
+            let expected = max_prod_fast_int(&a);
+
+            assert_eq!(max_prod_auto_int(&a), expected, "n = {n} density = {density}");
+            assert_eq!(max_prod_fast_int_sparse(&a), expected, "n = {n} density = {density}");
+        }
+    }
+
+    #[test]
+    fn test_mostly_zeros() {
+        let mut a = vec![0u32; SPARSE_MIN_LEN];
+        assert!(mostly_zeros(&a));
+        assert!(!mostly_zeros(&a[1..]));
+
+        // two sampled elements that aren't zero, then a third
+        for x in a.iter_mut().step_by(SPARSE_MIN_LEN / SAMPLE_LEN).take(SPARSE_MAX_NON_ZERO) {
+            *x = 1;
+        }
+        assert!(mostly_zeros(&a));
+        a[SPARSE_MIN_LEN - SPARSE_MIN_LEN / SAMPLE_LEN] = 1;
+        assert!(!mostly_zeros(&a));
+    }
+}
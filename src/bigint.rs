@@ -0,0 +1,112 @@
+use num::{BigUint, One, Zero};
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but for arbitrarily large
+/// integers, so the product can't overflow.
+///
+/// ```
+/// use num::BigUint;
+/// use max_prod::max_prod_bigint;
+///
+/// let a: Vec<BigUint> = [2u32, 0, 3, 4].into_iter().map(BigUint::from).collect();
+/// assert_eq!(max_prod_bigint(&a), (2, 3));
+/// ```
+#[must_use]
+pub fn max_prod_bigint(arr: impl AsRef<[BigUint]>) -> (usize, usize) {
+    let (_, i, j) = max_prod_bigint_with_value(arr);
+    (i, j)
+}
+
+/// Like [`max_prod_bigint`] but also returns the product of the winning range.
+///
+/// ```
+/// use num::BigUint;
+/// use max_prod::max_prod_bigint_with_value;
+///
+/// // far beyond u64
+/// let a = vec![BigUint::from(u64::MAX), BigUint::from(0u32), BigUint::from(u64::MAX), BigUint::from(u64::MAX)];
+/// let (p, i, j) = max_prod_bigint_with_value(&a);
+/// assert_eq!((i, j), (2, 3));
+/// assert_eq!(p, BigUint::from(u64::MAX) * BigUint::from(u64::MAX));
+/// ```
+#[must_use]
+pub fn max_prod_bigint_with_value(arr: impl AsRef<[BigUint]>) -> (BigUint, usize, usize) {
+    let arr = arr.as_ref();
+    let mut best: Option<(BigUint, usize, usize)> = None;
+
+    let n = arr.len();
+    let mut i = 0;
+
+    while i < n {
+        if arr[i].is_zero() {
+            i += 1;
+            continue;
+        }
+
+        // the product of a run only grows, so the best range of the run ends at its
+        // last element larger than one and only one product per run has to be compared
+        let start = i;
+        let mut end = i;
+        let mut run_prod = BigUint::one();
+
+        while i < n && !arr[i].is_zero() {
+            if !arr[i].is_one() {
+                run_prod *= &arr[i];
+                end = i;
+            }
+            i += 1;
+        }
+
+        // like `consider`, which needs `Copy`
+        if best.as_ref().is_none_or(|(p, _, _)| run_prod > *p) {
+            best = Some((run_prod, start, end));
+        }
+    }
+
+    best.unwrap_or((BigUint::zero(), 0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::{max_prod_fast_int, max_prod_fast_int_with_value};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn big(arr: &[u64]) -> Vec<BigUint> {
+        arr.iter().map(|&x| BigUint::from(x)).collect()
+    }
+
+    #[test]
+    fn test_bigint_basic() {
+        assert_eq!(max_prod_bigint(big(&[2, 0, 3, 4, 0, 5])), (2, 3));
+        assert_eq!(max_prod_bigint(big(&[1, 1, 5, 1, 0, 4])), (0, 2));
+        assert_eq!(max_prod_bigint(big(&[0, 1, 1])), (1, 1));
+        assert_eq!(max_prod_bigint(big(&[0, 0])), (0, 0));
+        assert_eq!(max_prod_bigint(big(&[])), (0, 0));
+    }
+
+    #[test]
+    fn test_bigint_huge() {
+        let arr = big(&[3, u64::MAX, u64::MAX, 0, u64::MAX, u64::MAX, 2]);
+        let (p, i, j) = max_prod_bigint_with_value(&arr);
+
+        assert!(p > BigUint::from(u128::MAX));
+        assert_eq!(p, BigUint::from(u64::MAX).pow(2) * 3u32);
+        assert_eq!((i, j), (0, 2));
+    }
+
+    #[test]
+    fn test_random_bigint() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(30).collect();
+            let (p, i, j) = max_prod_fast_int_with_value(&a);
+
+            assert_eq!(max_prod_bigint(big(&a)), max_prod_fast_int(&a));
+            assert_eq!(max_prod_bigint_with_value(big(&a)), (BigUint::from(p), i, j));
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::int::MaxProdInt;
+use crate::max_prod::{max_prod_fast_int_with_value, prod};
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but treats `arr` as a ring buffer.
+///
+/// A result with `i > j` is a wrapping range covering `arr[i..]` followed by `arr[..=j]`.
+/// Zeros still separate ranges, so a range can only wrap if both ends of the array are
+/// non-zero. On ties the range with the smallest start and then the shortest length wins,
+/// which means a linear range is preferred over an equally good wrapping one.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_circular;
+///
+/// // the best range wraps around, 2 * 1 * 4 * 3
+/// assert_eq!(max_prod_fast_int_circular(&[3u32, 0, 2, 1, 4]), (2, 0));
+/// assert_eq!(max_prod_fast_int_circular(&[0u32, 2, 3, 0]), (1, 2));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_circular<T: MaxProdInt>(arr: &[T]) -> (usize, usize) {
+    let (max_prod, i, j) = max_prod_fast_int_with_value(arr);
+
+    let n = arr.len();
+    let first_zero = match arr.iter().position(|&x| x == T::zero()) {
+        Some(z) => z,
+        None => return (i, j), // the whole ring is a single run, nothing to gain from wrapping
+    };
+
+    if first_zero == 0 || arr[n - 1] == T::zero() {
+        return (i, j);
+    }
+
+    let last_zero = arr.iter().rposition(|&x| x == T::zero()).unwrap();
+
+    // the wrapping range starts at the beginning of the trailing run and ends
+    // at the last element of the leading run that isn't a one
+    let start = last_zero + 1;
+    let end = (0..first_zero).rev().find(|&k| arr[k] != T::one()).unwrap_or(0);
+    let wrap_prod = prod(arr, start, n - 1) * prod(arr, 0, end);
+
+    if wrap_prod > max_prod || (wrap_prod == max_prod && start < i) {
+        (start, end)
+    } else {
+        (i, j)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::traits::Num;
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn max_prod_brute_force_circular<T: Num + Copy + PartialOrd>(arr: &[T]) -> (usize, usize) {
+        let mut max_prod = T::zero();
+        let mut max = (0, 0);
+        let n = arr.len();
+
+        // conceptually runs over the doubled array, limited to windows of at most n elements
+        for i in 0..n {
+            let mut prod = T::one();
+            for len in 1..=n {
+                let j = (i + len - 1) % n;
+                prod = prod * arr[j];
+
+                if prod > max_prod {
+                    max_prod = prod;
+                    max = (i, j);
+                }
+            }
+        }
+
+        max
+    }
+
+    #[test]
+    fn test_circular_basic() {
+        let a = vec![3u32, 2, 0, 5, 0, 4];
+        assert_eq!(max_prod_fast_int_circular(&a), (5, 1));
+
+        let a = vec![3u32, 2, 0, 30, 0, 4];
+        assert_eq!(max_prod_fast_int_circular(&a), (3, 3));
+
+        let a = vec![0u32, 2, 3, 0];
+        assert_eq!(max_prod_fast_int_circular(&a), (1, 2));
+
+        let a = vec![2u32, 1, 3];
+        assert_eq!(max_prod_fast_int_circular(&a), (0, 2));
+
+        let a = vec![1u32, 0, 2, 1];
+        assert_eq!(max_prod_fast_int_circular(&a), (2, 2));
+    }
+
+    #[test]
+    fn test_random_circular() {
+        let mut rng = test_rng();
+        for _ in 0..2000 {
+            let n = rng.gen_range(1..12);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(n).collect();
+            assert_eq!(max_prod_fast_int_circular(&a), max_prod_brute_force_circular(&a), "a = {:?}", a);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use num::{Float, ToPrimitive};
+use num::traits::CheckedMul;
+
+use crate::constrained::max_prod_fast_int_min_len;
+use crate::error::ConfigError;
+use crate::int::MaxProdInt;
+use crate::log_domain::{max_prod_fast_real_log, max_prod_log_int};
+use crate::max_prod::max_prod_fast_int_checked;
+use crate::real::max_prod_fast_real;
+use crate::tie_break::{max_prod_fast_int_with_tiebreak, TieBreak};
+
+/// Picks the algorithm from a few settings instead of its name.
+///
+/// ```
+/// use max_prod::{MaxProdConfig, TieBreak};
+///
+/// let config = MaxProdConfig::new().tie_break(TieBreak::Shortest).checked(true);
+/// assert_eq!(config.run_int([0u32, 1, 1, 6, 0, 2, 3]), Ok(Some((3, 3))));
+///
+/// let config = MaxProdConfig::new().log_domain(true);
+/// assert_eq!(config.run_real([0.5, 2.0, 0.25, 3.0]), Ok(Some((3, 3))));
+/// ```
+///
+/// Not every combination is supported, `tie_break` and `log_domain` can't be combined with
+/// each other or with `min_len`, and the real algorithms only support `log_domain`. Those
+/// combinations fail with [`ConfigError::Unsupported`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MaxProdConfig {
+    tie_break: TieBreak,
+    min_len: usize,
+    checked: bool,
+    log_domain: bool,
+}
+
+impl MaxProdConfig {
+    /// Same as [`max_prod_fast_int`](crate::max_prod_fast_int) and
+    /// [`max_prod_fast_real`](crate::max_prod_fast_real).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Which range wins if several have the maximum product, see
+    /// [`max_prod_fast_int_with_tiebreak`](crate::max_prod_fast_int_with_tiebreak).
+    #[must_use]
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Only ranges with at least `min_len` elements, see
+    /// [`max_prod_fast_int_min_len`](crate::max_prod_fast_int_min_len).
+    #[must_use]
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Fail with [`ConfigError::Overflow`] instead of wrapping, see
+    /// [`max_prod_fast_int_checked`](crate::max_prod_fast_int_checked). Floats don't
+    /// overflow, so the real algorithms ignore it.
+    #[must_use]
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Compare sums of logarithms instead of products, see
+    /// [`max_prod_log_int`](crate::max_prod_log_int) and
+    /// [`max_prod_fast_real_log`](crate::max_prod_fast_real_log). Nothing can overflow
+    /// then, so `checked` has no effect.
+    #[must_use]
+    pub fn log_domain(mut self, log_domain: bool) -> Self {
+        self.log_domain = log_domain;
+        self
+    }
+
+    fn has_tie_break(&self) -> bool {
+        self.tie_break != TieBreak::default()
+    }
+
+    /// Runs the integer algorithm the settings ask for.
+    ///
+    /// Returns `None` if `arr` is empty or shorter than `min_len`.
+    pub fn run_int<T: MaxProdInt + CheckedMul + ToPrimitive>(&self, arr: impl AsRef<[T]>) -> Result<Option<(usize, usize)>, ConfigError> {
+        let arr = arr.as_ref();
+        let min_len = self.min_len.max(1);
+
+        match (self.has_tie_break(), min_len > 1, self.log_domain) {
+            (true, true, _) => return Err(ConfigError::Unsupported("tie_break with min_len")),
+            (true, _, true) => return Err(ConfigError::Unsupported("tie_break with log_domain")),
+            (_, true, true) => return Err(ConfigError::Unsupported("min_len with log_domain")),
+            _ => {}
+        }
+
+        if arr.len() < min_len {
+            return Ok(None);
+        }
+
+        if self.log_domain {
+            let (_, i, j) = max_prod_log_int(arr);
+            return Ok(Some((i, j)));
+        }
+
+        // the same running products as the algorithms below, so if it doesn't overflow
+        // they don't either
+        if self.checked {
+            max_prod_fast_int_checked(arr)?;
+        }
+
+        if min_len > 1 {
+            return Ok(max_prod_fast_int_min_len(arr, min_len));
+        }
+
+        Ok(Some(max_prod_fast_int_with_tiebreak(arr, self.tie_break)))
+    }
+
+    /// Runs the real algorithm the settings ask for.
+    ///
+    /// Returns `None` if `arr` is empty.
+    pub fn run_real<T: Float>(&self, arr: impl AsRef<[T]>) -> Result<Option<(usize, usize)>, ConfigError> {
+        let arr = arr.as_ref();
+
+        if self.has_tie_break() {
+            return Err(ConfigError::Unsupported("tie_break for reals"));
+        }
+        if self.min_len > 1 {
+            return Err(ConfigError::Unsupported("min_len for reals"));
+        }
+
+        if arr.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(if self.log_domain {
+            max_prod_fast_real_log(arr)
+        } else {
+            max_prod_fast_real(arr)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::error::OverflowError;
+    use crate::max_prod::max_prod_fast_int;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_config_int() {
+        let a = [0u32, 1, 1, 6, 0, 2, 3, 0, 2, 2, 1];
+
+        assert_eq!(MaxProdConfig::new().run_int(a), Ok(Some((1, 3))));
+        assert_eq!(MaxProdConfig::new().tie_break(TieBreak::Shortest).run_int(a), Ok(Some((3, 3))));
+        assert_eq!(MaxProdConfig::new().tie_break(TieBreak::Longest).checked(true).run_int(a), Ok(Some((1, 3))));
+        assert_eq!(MaxProdConfig::new().min_len(4).checked(true).run_int(a), Ok(Some((0, 3))));
+        assert_eq!(MaxProdConfig::new().min_len(12).run_int(a), Ok(None));
+        assert_eq!(MaxProdConfig::new().log_domain(true).run_int(a), Ok(Some((1, 3))));
+        assert_eq!(MaxProdConfig::new().run_int::<u32>([]), Ok(None));
+    }
+
+    #[test]
+    fn test_config_checked() {
+        let a = [16u8, 16, 0, 3];
+
+        assert_eq!(MaxProdConfig::new().checked(true).run_int(a), Err(ConfigError::Overflow(OverflowError { start: 0, end: 1 })));
+        assert_eq!(MaxProdConfig::new().checked(true).min_len(2).run_int(a), Err(ConfigError::Overflow(OverflowError { start: 0, end: 1 })));
+        // nothing overflows in the log domain
+        assert_eq!(MaxProdConfig::new().checked(true).log_domain(true).run_int(a), Ok(Some((0, 1))));
+    }
+
+    #[test]
+    fn test_config_unsupported() {
+        let a = [2u32, 3];
+
+        let config = MaxProdConfig::new().tie_break(TieBreak::Rightmost).min_len(2);
+        assert!(matches!(config.run_int(a), Err(ConfigError::Unsupported(_))));
+        assert!(matches!(config.log_domain(true).run_int(a), Err(ConfigError::Unsupported(_))));
+        assert!(matches!(MaxProdConfig::new().min_len(2).log_domain(true).run_int(a), Err(ConfigError::Unsupported(_))));
+
+        assert!(matches!(MaxProdConfig::new().min_len(2).run_real([2.0, 3.0]), Err(ConfigError::Unsupported(_))));
+        assert!(matches!(MaxProdConfig::new().tie_break(TieBreak::Shortest).run_real([2.0]), Err(ConfigError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_config_real() {
+        let a = [0.5, 2.0, 0.25, 3.0, f64::NAN, 1.5];
+
+        assert_eq!(MaxProdConfig::new().run_real(a), Ok(Some((3, 3))));
+        assert_eq!(MaxProdConfig::new().checked(true).log_domain(true).run_real(a), Ok(Some((3, 3))));
+        assert_eq!(MaxProdConfig::new().run_real::<f32>([]), Ok(None));
+    }
+
+    #[test]
+    fn test_random_config_default() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(1..20);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 5).sample_iter(&mut rng).take(n).collect();
+
+            assert_eq!(MaxProdConfig::new().run_int(&a), Ok(Some(max_prod_fast_int(&a))));
+            assert_eq!(MaxProdConfig::new().min_len(1).checked(true).run_int(&a), Ok(Some(max_prod_fast_int(&a))));
+        }
+    }
+}
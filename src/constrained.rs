@@ -0,0 +1,208 @@
+use crate::int::MaxProdInt;
+use crate::max_prod::prod;
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but only considers ranges with
+/// `j - i + 1 >= min_len`.
+///
+/// Returns `None` if the array is shorter than `min_len`. If no run of non-zero elements
+/// is long enough every valid range has product zero and the first one is returned.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_min_len;
+///
+/// let a = [7u32, 0, 2, 3];
+/// assert_eq!(max_prod_fast_int_min_len(&a, 1), Some((0, 0)));
+/// assert_eq!(max_prod_fast_int_min_len(&a, 2), Some((2, 3)));
+/// assert_eq!(max_prod_fast_int_min_len(&a, 5), None);
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_min_len<T: MaxProdInt>(arr: &[T], min_len: usize) -> Option<(usize, usize)> {
+    let min_len = min_len.max(1);
+    let n = arr.len();
+
+    if n < min_len {
+        return None;
+    }
+
+    let mut max_prod = T::zero();
+    let mut max = (0, min_len - 1);
+    let mut s = 0;
+
+    while s < n {
+        if arr[s] == T::zero() {
+            s += 1;
+            continue;
+        }
+
+        let mut e = s;
+        while e + 1 < n && arr[e + 1] != T::zero() {
+            e += 1;
+        }
+
+        if e - s + 1 >= min_len {
+            // extending a range inside a run never lowers its product, so the best
+            // range starts at the run start and has to reach the last element that
+            // isn't a one
+            let p = prod(arr, s, e);
+            let b = (s..=e).rev().find(|&k| arr[k] != T::one()).unwrap_or(s);
+
+            if p > max_prod {
+                max_prod = p;
+                max = (s, b.max(s + min_len - 1));
+            }
+        }
+
+        s = e + 1;
+    }
+
+    Some(max)
+}
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but only considers ranges that
+/// contain `idx`, panics if `idx` is out of bounds.
+///
+/// If `arr[idx]` is zero every such range has product zero and `(idx, idx)` is returned.
+/// Otherwise the range grows from `idx` inside its run of non-zero elements, all the way
+/// to the run start and up to the last element after `idx` that isn't a one.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_containing;
+///
+/// let a = [3u32, 0, 1, 2, 1, 0, 9];
+/// assert_eq!(max_prod_fast_int_containing(a, 4), (2, 4));
+/// assert_eq!(max_prod_fast_int_containing(a, 2), (2, 3));
+/// assert_eq!(max_prod_fast_int_containing(a, 5), (5, 5));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_containing<T: MaxProdInt>(arr: impl AsRef<[T]>, idx: usize) -> (usize, usize) {
+    let arr = arr.as_ref();
+
+    if arr[idx] == T::zero() {
+        return (idx, idx);
+    }
+
+    let mut s = idx;
+    while s > 0 && arr[s - 1] != T::zero() {
+        s -= 1;
+    }
+
+    let mut e = idx;
+    let mut k = idx;
+    while k + 1 < arr.len() && arr[k + 1] != T::zero() {
+        k += 1;
+        if arr[k] != T::one() {
+            e = k;
+        }
+    }
+
+    (s, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::max_prod_fast_int;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn max_prod_brute_force_min_len(arr: &[u64], min_len: usize) -> Option<(usize, usize)> {
+        let n = arr.len();
+        let mut max: Option<(u64, usize, usize)> = None;
+
+        for i in 0..n {
+            for j in (i + min_len.max(1) - 1)..n {
+                let p = prod(arr, i, j);
+
+                if max.is_none_or(|(m, _, _)| p > m) {
+                    max = Some((p, i, j));
+                }
+            }
+        }
+
+        max.map(|(_, i, j)| (i, j))
+    }
+
+    fn max_prod_brute_force_containing(arr: &[u64], idx: usize) -> (usize, usize) {
+        let mut max = (arr[idx], idx, idx);
+
+        for i in 0..=idx {
+            for j in idx..arr.len() {
+                let p = prod(arr, i, j);
+
+                if p > max.0 || (p == max.0 && (i, j) < (max.1, max.2)) {
+                    max = (p, i, j);
+                }
+            }
+        }
+
+        if max.0 == 0 { (idx, idx) } else { (max.1, max.2) }
+    }
+
+    #[test]
+    fn test_min_len_basic() {
+        let a = vec![5u32, 0, 2, 3, 0, 7];
+        assert_eq!(max_prod_fast_int_min_len(&a, 1), Some((5, 5)));
+        assert_eq!(max_prod_fast_int_min_len(&a, 2), Some((2, 3)));
+        assert_eq!(max_prod_fast_int_min_len(&a, 3), Some((0, 2)));
+        assert_eq!(max_prod_fast_int_min_len(&a, 6), Some((0, 5)));
+        assert_eq!(max_prod_fast_int_min_len(&a, 7), None);
+
+        let a = vec![2u32, 1, 1, 1];
+        assert_eq!(max_prod_fast_int_min_len(&a, 3), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_min_len_unconstrained() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 5).sample_iter(&mut rng).take(20).collect();
+            assert_eq!(max_prod_fast_int_min_len(&a, 0), Some(max_prod_fast_int(&a)));
+            assert_eq!(max_prod_fast_int_min_len(&a, 1), Some(max_prod_fast_int(&a)));
+        }
+    }
+
+    #[test]
+    fn test_random_min_len() {
+        let mut rng = test_rng();
+        for _ in 0..2000 {
+            let n = rng.gen_range(0..12);
+            let min_len = rng.gen_range(0..8);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 3).sample_iter(&mut rng).take(n).collect();
+
+            assert_eq!(max_prod_fast_int_min_len(&a, min_len), max_prod_brute_force_min_len(&a, min_len), "a = {:?} min_len = {}", a, min_len);
+        }
+    }
+
+    #[test]
+    fn test_containing_basic() {
+        let a = vec![2u32, 1, 0, 1, 1, 3, 1, 0];
+        assert_eq!(max_prod_fast_int_containing(&a, 0), (0, 0));
+        assert_eq!(max_prod_fast_int_containing(&a, 1), (0, 1));
+        assert_eq!(max_prod_fast_int_containing(&a, 2), (2, 2));
+        assert_eq!(max_prod_fast_int_containing(&a, 3), (3, 5));
+        assert_eq!(max_prod_fast_int_containing(&a, 6), (3, 6));
+        assert_eq!(max_prod_fast_int_containing(&a, 7), (7, 7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_containing_out_of_bounds() {
+        let _ = max_prod_fast_int_containing([1u32, 2], 2);
+    }
+
+    #[test]
+    fn test_random_containing() {
+        let mut rng = test_rng();
+        for _ in 0..2000 {
+            let n = rng.gen_range(1..12);
+            let idx = rng.gen_range(0..n);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 3).sample_iter(&mut rng).take(n).collect();
+
+            assert_eq!(max_prod_fast_int_containing(&a, idx), max_prod_brute_force_containing(&a, idx), "a = {:?} idx = {}", a, idx);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use rust_decimal::Decimal;
+
+use crate::rational::max_prod_fast_rational_with_value;
+
+/// [`max_prod_fast_rational`](crate::max_prod_fast_rational) for exact decimals such as
+/// returns or growth factors.
+///
+/// `Decimal` implements `Num`, ordering and multiplication are all the algorithm needs,
+/// segments are split at exactly `Decimal::ONE`. Products are exact as long as they fit
+/// into the 28 digits of a `Decimal`, beyond that it rounds the least significant digits,
+/// and if a product overflows `Decimal::MAX` multiplying panics.
+///
+/// ```
+/// use rust_decimal::Decimal;
+/// use max_prod::max_prod_fast_decimal;
+///
+/// let factors: Vec<Decimal> = ["0.97", "1.05", "1.02", "0.99", "1.03", "0.90"].iter().map(|s| s.parse().unwrap()).collect();
+/// assert_eq!(max_prod_fast_decimal(&factors), (1, 4));
+/// ```
+#[must_use]
+pub fn max_prod_fast_decimal(arr: &[Decimal]) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_decimal_with_value(arr);
+    (i, j)
+}
+
+/// Like [`max_prod_fast_decimal`] but also returns the product of the winning range.
+///
+/// ```
+/// use rust_decimal::Decimal;
+/// use max_prod::max_prod_fast_decimal_with_value;
+///
+/// let factors: Vec<Decimal> = ["1.10", "0.50", "1.20", "1.50"].iter().map(|s| s.parse().unwrap()).collect();
+/// assert_eq!(max_prod_fast_decimal_with_value(&factors), ("1.8".parse().unwrap(), 2, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_decimal_with_value(arr: &[Decimal]) -> (Decimal, usize, usize) {
+    max_prod_fast_rational_with_value(arr)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use crate::max_prod::{max_prod_brute_force_improved_with_value, prod};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_decimal_around_one() {
+        // 1.05 * 1.02 * 0.99 * 1.01 = 1.0708929 is just below 1.05 * 1.02 = 1.071
+        let arr = [d("0.97"), d("1.05"), d("1.02"), d("0.99"), d("1.01"), d("0.90")];
+        assert_eq!(max_prod_fast_decimal_with_value(&arr), (d("1.071"), 1, 2));
+
+        let arr = [d("0.97"), d("1.05"), d("1.02"), d("0.99"), d("1.03"), d("0.90")];
+        assert_eq!(max_prod_fast_decimal_with_value(&arr), (d("1.0920987"), 1, 4));
+
+        // exactly one, the shorter range wins like in the brute force
+        let arr = [d("1.25"), d("0.8"), d("1.25")];
+        assert_eq!(max_prod_fast_decimal_with_value(&arr), (d("1.25"), 0, 0));
+
+        let arr = [d("0.5"), d("0.97"), d("0")];
+        assert_eq!(max_prod_fast_decimal_with_value(&arr), (d("0.97"), 1, 1));
+        assert_eq!(max_prod_fast_decimal(&[]), (0, 0));
+    }
+
+    #[test]
+    fn test_random_decimal() {
+        let mut rng = test_rng();
+        for _ in 0..300 {
+            // factors between 0.90 and 1.10 with two digits, the products of up to 12 of them
+            // fit into the 28 digits, so they are exact in any order
+            let n = rng.gen_range(1..=12);
+            let arr: Vec<Decimal> = (0..n).map(|_| Decimal::new(rng.gen_range(90..=110), 2)).collect();
+
+            let (p, i, j) = max_prod_fast_decimal_with_value(&arr);
+            assert_eq!((p, i, j), max_prod_brute_force_improved_with_value(&arr), "{arr:?}");
+            assert_eq!(prod(&arr, i, j), p, "{arr:?}");
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use num::traits::real::Real;
+
+/// Classic divide-and-conquer algorithm for positive reals.
+///
+/// The array is split in half, both halves are solved recursively and the best range
+/// crossing the midpoint is built from the best suffix product of the left half and
+/// the best prefix product of the right half. Every level does O(n) work, so this runs
+/// in O(n log n). Returns the same range as
+/// [`max_prod_brute_force_improved`](crate::max_prod_brute_force_improved).
+///
+/// ```
+/// use max_prod::max_prod_divide_conquer_real;
+///
+/// assert_eq!(max_prod_divide_conquer_real(&[0.5, 2.0, 3.0, 0.1, 4.0]), (1, 2));
+/// ```
+#[must_use]
+pub fn max_prod_divide_conquer_real<T: Real + Copy>(arr: &[T]) -> (usize, usize) {
+    if arr.is_empty() {
+        return (0, 0);
+    }
+
+    let (_, i, j) = divide_conquer(arr, 0, arr.len() - 1);
+    (i, j)
+}
+
+fn divide_conquer<T: Real + Copy>(arr: &[T], lo: usize, hi: usize) -> (T, usize, usize) {
+    if lo == hi {
+        return (arr[lo], lo, lo);
+    }
+
+    let mid = lo + (hi - lo) / 2;
+    let left = divide_conquer(arr, lo, mid);
+    let right = divide_conquer(arr, mid + 1, hi);
+
+    // the crossing range is arr[i..=mid] followed by arr[mid + 1..=j], for positive
+    // values both halves can be maximized independently, ties prefer the smaller index
+    let mut suffix = (arr[mid], mid);
+    let mut prod = arr[mid];
+    for i in (lo..mid).rev() {
+        prod = prod * arr[i];
+        if prod >= suffix.0 {
+            suffix = (prod, i);
+        }
+    }
+
+    let mut prefix = (arr[mid + 1], mid + 1);
+    let mut prod = arr[mid + 1];
+    for (j, &x) in (mid + 2..).zip(&arr[mid + 2..=hi]) {
+        prod = prod * x;
+        if prod > prefix.0 {
+            prefix = (prod, j);
+        }
+    }
+
+    let crossing = (suffix.0 * prefix.0, suffix.1, prefix.1);
+
+    // candidates are ordered by their start, so only a strictly larger product wins
+    let mut best = left;
+    for candidate in [crossing, right] {
+        if candidate.0 > best.0 || (candidate.0 == best.0 && (candidate.1, candidate.2) < (best.1, best.2)) {
+            best = candidate;
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divide_conquer_basic() {
+        let arr = vec![0.1, 0.5, 13.0, 2.0, 0.1, 4.0, 6.0, 7.0, 8.0, 0.1, 0.2];
+        assert_eq!(max_prod_divide_conquer_real(&arr), (2, 8));
+
+        let arr = vec![0.5, 0.25];
+        assert_eq!(max_prod_divide_conquer_real(&arr), (0, 0));
+
+        let arr = vec![3.0];
+        assert_eq!(max_prod_divide_conquer_real(&arr), (0, 0));
+
+        let arr: Vec<f64> = vec![];
+        assert_eq!(max_prod_divide_conquer_real(&arr), (0, 0));
+    }
+
+    #[test]
+    fn test_divide_conquer_ties() {
+        let arr = vec![2.0, 0.5, 2.0, 1.0, 2.0];
+        assert_eq!(max_prod_divide_conquer_real(&arr), (0, 4));
+
+        let arr = vec![1.0, 1.0, 1.0];
+        assert_eq!(max_prod_divide_conquer_real(&arr), (0, 0));
+    }
+}
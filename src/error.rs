@@ -0,0 +1,99 @@
+use core::fmt::{Display, Formatter};
+
+/// Returned by the checked algorithms when the product of `arr[start..=end]` overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "product of range {} .. {} overflows", self.start, self.end)
+    }
+}
+
+impl core::error::Error for OverflowError {}
+
+/// Returned by the `_as` algorithms when an index of the result doesn't fit into the
+/// requested index type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOverflowError {
+    pub index: usize,
+}
+
+impl Display for IndexOverflowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "index {} doesn't fit into the index type", self.index)
+    }
+}
+
+impl core::error::Error for IndexOverflowError {}
+
+/// Returned by the matrix algorithms when not all rows have the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonRectangularError {
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for NonRectangularError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "row {} has {} columns, expected {}", self.row, self.found, self.expected)
+    }
+}
+
+impl core::error::Error for NonRectangularError {}
+
+/// Returned by [`MaxProdConfig`](crate::MaxProdConfig) when it can't run with its settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `checked` is set and a product overflows.
+    Overflow(OverflowError),
+    /// No algorithm supports this combination of settings.
+    Unsupported(&'static str),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConfigError::Overflow(e) => e.fmt(f),
+            ConfigError::Unsupported(settings) => write!(f, "unsupported settings: {}", settings),
+        }
+    }
+}
+
+impl core::error::Error for ConfigError {}
+
+impl From<OverflowError> for ConfigError {
+    fn from(e: OverflowError) -> Self {
+        ConfigError::Overflow(e)
+    }
+}
+
+/// Returned by the `try_` algorithms instead of panicking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaxProdError {
+    /// The input slice is empty.
+    Empty,
+    /// The algorithm ended up with a range whose start lies after its end.
+    InvalidRange { start: usize, end: usize },
+    /// Compressing a non-empty input produced no segments.
+    EmptyCompression,
+    /// The element at `index` is negative, which the real algorithms don't support.
+    Negative { index: usize },
+}
+
+impl Display for MaxProdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MaxProdError::Empty => write!(f, "input is empty"),
+            MaxProdError::InvalidRange { start, end } => write!(f, "invalid range {} .. {}", start, end),
+            MaxProdError::EmptyCompression => write!(f, "compression produced no segments"),
+            MaxProdError::Negative { index } => write!(f, "negative element at index {}", index),
+        }
+    }
+}
+
+impl core::error::Error for MaxProdError {}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+
+use twofloat::TwoFloat;
+
+use crate::real::{combine_compressed, compress_dual_with, max_prod_fast_real, nan_as_zero, widest_inf_run};
+
+/// Like [`max_prod_fast_real`](crate::max_prod_fast_real) but multiplies the products in
+/// double-double precision with [`TwoFloat`], an unevaluated sum of two `f64`s with about
+/// 106 bits of mantissa.
+///
+/// `f64` products of values close to one round away differences below `1e-16`, so two
+/// ranges whose products differ by less than that are taken as equal and the wrong one can
+/// win. The rounding error of double-double products is about `1e-32`. The exponent range
+/// is still the one of `f64`, for products that underflow use
+/// [`max_prod_fast_real_log`](crate::max_prod_fast_real_log). Well-conditioned inputs
+/// return the same range as `max_prod_fast_real`, at a few times its cost.
+///
+/// ```
+/// use max_prod::{max_prod_fast_real, max_prod_fast_real_hp};
+///
+/// // (1 + 2^-30)^2 is larger than 1 + 2^-29 by 2^-60, which f64 rounds away
+/// let e = 2f64.powi(-30);
+/// let arr = [1.0 + 2.0 * e, 0.5, 1.0 + e, 1.0 + e];
+/// assert_eq!(max_prod_fast_real(arr), (0, 0));
+/// assert_eq!(max_prod_fast_real_hp(arr), (2, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_hp(arr: impl AsRef<[f64]>) -> (usize, usize) {
+    let arr = arr.as_ref();
+
+    // these cases return without comparing any products
+    if arr.is_empty() || widest_inf_run(arr).is_some() || arr.iter().all(|&x| x >= 1.0) {
+        return max_prod_fast_real(arr);
+    }
+
+    let hp: Vec<TwoFloat> = arr.iter().map(|&x| TwoFloat::from(nan_as_zero(x))).collect();
+    let (_, i, j) = combine_compressed(compress_dual_with(&hp, |x| x).unwrap()).unwrap();
+    (i, j)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use num::rational::Ratio;
+
+    use crate::max_prod::max_prod_brute_force_improved;
+    use crate::rational::max_prod_fast_rational;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_hp_near_tie() {
+        // exactly (1 + 2^-30)^2 = 1 + 2^-29 + 2^-60 against 1 + 2^-29
+        let e = 2f64.powi(-30);
+        let arr = [1.0 + 2.0 * e, 0.5, 1.0 + e, 1.0 + e];
+
+        let d = 1i128 << 30;
+        let exact = [Ratio::new(d + 2, d), Ratio::new(1, 2), Ratio::new(d + 1, d), Ratio::new(d + 1, d)];
+        assert_eq!(max_prod_fast_rational(exact), (2, 3));
+
+        assert_eq!(max_prod_fast_real_hp(arr), (2, 3));
+        // f64 sees a tie and keeps the first range
+        assert_eq!(max_prod_fast_real(arr), (0, 0));
+        assert_eq!(max_prod_brute_force_improved(arr), (0, 0));
+    }
+
+    #[test]
+    fn test_hp_special() {
+        assert_eq!(max_prod_fast_real_hp([]), (0, 0));
+        assert_eq!(max_prod_fast_real_hp([0.5]), (0, 0));
+        assert_eq!(max_prod_fast_real_hp([2.0, 1.0, 3.0, 1.0]), (0, 2));
+        assert_eq!(max_prod_fast_real_hp([0.5, f64::INFINITY, 2.0]), (1, 1));
+        assert_eq!(max_prod_fast_real_hp([3.0, f64::NAN, 2.0, 2.0, 0.1]), (2, 3));
+        assert_eq!(max_prod_fast_real_hp([3.0, f64::NEG_INFINITY, 0.5, 2.0]), (0, 0));
+    }
+
+    #[test]
+    fn test_random_hp() {
+        let mut rng = test_rng();
+        let special = [0.0, 1.0, f64::NAN];
+
+        for _ in 0..500 {
+            let n = rng.gen_range(1..60);
+            let mut a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            for x in &mut a {
+                if rng.gen_bool(0.05) {
+                    *x = special[rng.gen_range(0..special.len())];
+                }
+            }
+
+            assert_eq!(max_prod_fast_real_hp(&a), max_prod_fast_real(&a), "{a:?}");
+        }
+    }
+}
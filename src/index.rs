@@ -0,0 +1,77 @@
+#[cfg(feature = "alloc")]
+use num::traits::real::Real;
+
+use crate::error::IndexOverflowError;
+use crate::int::MaxProdInt;
+use crate::max_prod::max_prod_fast_int;
+#[cfg(feature = "alloc")]
+use crate::real::max_prod_fast_real;
+
+/// Converts both indices of `range` into `I`, e.g. to store many results compactly.
+///
+/// ```
+/// use max_prod::{narrow_range, IndexOverflowError};
+///
+/// assert_eq!(narrow_range::<u8>((3, 200)), Ok((3, 200)));
+/// assert_eq!(narrow_range::<u8>((3, 300)), Err(IndexOverflowError { index: 300 }));
+/// ```
+pub fn narrow_range<I: TryFrom<usize>>((start, end): (usize, usize)) -> Result<(I, I), IndexOverflowError> {
+    let narrow = |index: usize| I::try_from(index).map_err(|_| IndexOverflowError { index });
+    Ok((narrow(start)?, narrow(end)?))
+}
+
+/// Like [`max_prod_fast_int`] but returns the range as `(I, I)`, fails if an index doesn't fit.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_as;
+///
+/// let (i, j): (u8, u8) = max_prod_fast_int_as([2u32, 0, 3, 4]).unwrap();
+/// assert_eq!((i, j), (2, 3));
+///
+/// // the best element is at index 299
+/// let mut v = vec![0u32; 300];
+/// v[299] = 2;
+/// assert!(max_prod_fast_int_as::<u8, _>(&v).is_err());
+/// ```
+pub fn max_prod_fast_int_as<I: TryFrom<usize>, T: MaxProdInt>(arr: impl AsRef<[T]>) -> Result<(I, I), IndexOverflowError> {
+    narrow_range(max_prod_fast_int(arr))
+}
+
+/// Like [`max_prod_fast_real`] but returns the range as `(I, I)`, fails if an index doesn't fit.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_as;
+///
+/// assert_eq!(max_prod_fast_real_as::<u16, _>([0.5, 2.0, 3.0]), Ok((1, 2)));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn max_prod_fast_real_as<I: TryFrom<usize>, T: Real + Copy>(arr: impl AsRef<[T]>) -> Result<(I, I), IndexOverflowError> {
+    narrow_range(max_prod_fast_real(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_u16() {
+        assert_eq!(max_prod_fast_int_as::<u16, _>([2u32, 0, 3, 4]), Ok((2, 3)));
+        assert_eq!(max_prod_fast_real_as::<u16, _>([0.5, 4.0, 0.5, 3.0]), Ok((1, 3)));
+
+        let mut arr = vec![0u32; 70_000];
+        arr[65_535] = 2;
+        assert_eq!(max_prod_fast_int_as::<u16, _>(&arr), Ok((65_535, 65_535)));
+        assert_eq!(max_prod_fast_int_as::<u32, _>(&arr), Ok((65_535, 65_535)));
+
+        arr[65_536] = 3;
+        assert_eq!(max_prod_fast_int_as::<u16, _>(&arr), Err(IndexOverflowError { index: 65_536 }));
+        assert_eq!(max_prod_fast_int_as::<u32, _>(&arr), Ok((65_535, 65_536)));
+    }
+
+    #[test]
+    fn test_narrow_range_start() {
+        assert_eq!(narrow_range::<u16>((70_000, 70_001)), Err(IndexOverflowError { index: 70_000 }));
+        assert_eq!(narrow_range::<i8>((0, 127)), Ok((0, 127)));
+        assert_eq!(narrow_range::<i8>((0, 128)), Err(IndexOverflowError { index: 128 }));
+    }
+}
@@ -0,0 +1,130 @@
+use num::{Integer, Unsigned};
+use num::traits::Num;
+
+/// The unsigned integers the integer algorithms work with.
+///
+/// Implemented for every type that is `Num + Integer + Copy + Unsigned`, so it doesn't
+/// need to, and can't, be implemented by hand: a custom integer type gets it by
+/// implementing those traits. No algorithm needs `Display` or any other formatting.
+///
+/// ```
+/// use max_prod::{max_prod_fast_int, MaxProdInt};
+///
+/// fn best<T: MaxProdInt>(arr: &[T]) -> (usize, usize) {
+///     max_prod_fast_int(arr)
+/// }
+///
+/// assert_eq!(best(&[2u8, 0, 3, 4]), (2, 3));
+/// ```
+pub trait MaxProdInt: Num + Integer + Copy + Unsigned {}
+
+impl<T: Num + Integer + Copy + Unsigned> MaxProdInt for T {}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::{Add, Div, Mul, Rem, Sub};
+
+    use num::{One, Zero};
+
+    use crate::max_prod::{max_prod_brute_force, max_prod_fast_int_with_value};
+    use super::*;
+
+    /// A `u32` newtype that only implements the traits `MaxProdInt` needs.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    struct Count(u32);
+
+    macro_rules! impl_op {
+        ($($tr:ident $f:ident),*) => {
+            $(
+                impl $tr for Count {
+                    type Output = Count;
+
+                    fn $f(self, rhs: Count) -> Count {
+                        Count(self.0.$f(rhs.0))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_op!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+    impl Zero for Count {
+        fn zero() -> Self {
+            Count(0)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl One for Count {
+        fn one() -> Self {
+            Count(1)
+        }
+    }
+
+    impl Num for Count {
+        type FromStrRadixErr = <u32 as Num>::FromStrRadixErr;
+
+        fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+            u32::from_str_radix(s, radix).map(Count)
+        }
+    }
+
+    impl Integer for Count {
+        fn div_floor(&self, other: &Self) -> Self {
+            Count(Integer::div_floor(&self.0, &other.0))
+        }
+
+        fn mod_floor(&self, other: &Self) -> Self {
+            Count(Integer::mod_floor(&self.0, &other.0))
+        }
+
+        fn gcd(&self, other: &Self) -> Self {
+            Count(Integer::gcd(&self.0, &other.0))
+        }
+
+        fn lcm(&self, other: &Self) -> Self {
+            Count(Integer::lcm(&self.0, &other.0))
+        }
+
+        fn is_multiple_of(&self, other: &Self) -> bool {
+            Integer::is_multiple_of(&self.0, &other.0)
+        }
+
+        fn divides(&self, other: &Self) -> bool {
+            Integer::is_multiple_of(self, other)
+        }
+
+        fn is_even(&self) -> bool {
+            Integer::is_even(&self.0)
+        }
+
+        fn is_odd(&self) -> bool {
+            Integer::is_odd(&self.0)
+        }
+
+        fn div_rem(&self, other: &Self) -> (Self, Self) {
+            let (q, r) = Integer::div_rem(&self.0, &other.0);
+            (Count(q), Count(r))
+        }
+    }
+
+    impl Unsigned for Count {}
+
+    fn best<T: MaxProdInt>(arr: &[T]) -> (T, usize, usize) {
+        max_prod_fast_int_with_value(arr)
+    }
+
+    #[test]
+    fn test_newtype() {
+        let arr: Vec<Count> = [2, 3, 0, 6, 1, 1, 0, 5].into_iter().map(Count).collect();
+        assert_eq!(best(&arr), (Count(6), 0, 1));
+        assert_eq!(max_prod_brute_force(&arr), (0, 1));
+
+        let arr: Vec<Count> = [0, 2, 2, 0, 5].into_iter().map(Count).collect();
+        assert_eq!(best(&arr), (Count(5), 4, 4));
+    }
+}
@@ -0,0 +1,227 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+//! Algorithms for finding the consecutive subarray with the maximum product.
+//!
+//! The integer algorithms work without `std` and without an allocator. Everything
+//! that needs a `Vec` or works on reals is behind the `alloc` feature, which is
+//! enabled by the default `std` feature.
+//!
+//! With the `trace` feature the real algorithms log what they do through the `log` crate,
+//! to see it install a logger such as `env_logger` and run with `RUST_LOG=max_prod=trace`.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// `log::trace!` with the `trace` feature, nothing without it.
+#[cfg(feature = "alloc")]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "trace")]
+        log::trace!($($arg)*);
+    };
+}
+
+/// `log::debug!` with the `trace` feature, nothing without it.
+#[cfg(feature = "alloc")]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "trace")]
+        log::debug!($($arg)*);
+    };
+}
+
+mod accumulator;
+mod algorithm;
+mod auto;
+#[cfg(feature = "alloc")]
+mod all_max;
+#[cfg(feature = "alloc")]
+mod bigint;
+mod circular;
+#[cfg(feature = "alloc")]
+mod config;
+mod constrained;
+#[cfg(feature = "decimal")]
+mod decimal;
+#[cfg(feature = "alloc")]
+mod divide_conquer;
+mod error;
+#[cfg(feature = "hp")]
+mod hp;
+mod index;
+mod int;
+#[cfg(feature = "alloc")]
+mod log_domain;
+#[cfg(feature = "alloc")]
+mod matrix;
+mod max_prod;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+mod non_empty;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "alloc")]
+mod prefix;
+#[cfg(test)]
+mod proptests;
+#[cfg(feature = "alloc")]
+mod rational;
+#[cfg(feature = "alloc")]
+mod real;
+#[cfg(feature = "alloc")]
+mod real_f64;
+mod result;
+mod rle;
+#[cfg(feature = "alloc")]
+mod runs;
+mod score;
+#[cfg(feature = "alloc")]
+mod segtree;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(test)]
+mod snapshots;
+mod span;
+#[cfg(test)]
+mod test_rng;
+mod threshold;
+mod tie_break;
+#[cfg(feature = "alloc")]
+mod top_k;
+mod tracker;
+mod verify;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod widen;
+mod window;
+
+pub use accumulator::MaxProdAccumulator;
+pub use algorithm::{BruteForce, BruteForceImproved, FastInt, MaxProdAlgorithm};
+#[cfg(feature = "alloc")]
+pub use algorithm::FastReal;
+#[cfg(feature = "alloc")]
+pub use all_max::all_max_prod_fast_int;
+pub use auto::max_prod_auto_int;
+#[cfg(feature = "alloc")]
+pub use bigint::{max_prod_bigint, max_prod_bigint_with_value};
+pub use circular::max_prod_fast_int_circular;
+#[cfg(feature = "alloc")]
+pub use config::MaxProdConfig;
+pub use constrained::{max_prod_fast_int_containing, max_prod_fast_int_min_len};
+#[cfg(feature = "decimal")]
+pub use decimal::{max_prod_fast_decimal, max_prod_fast_decimal_with_value};
+#[cfg(feature = "alloc")]
+pub use divide_conquer::max_prod_divide_conquer_real;
+pub use error::{ConfigError, IndexOverflowError, MaxProdError, NonRectangularError, OverflowError};
+#[cfg(feature = "hp")]
+pub use hp::max_prod_fast_real_hp;
+pub use index::{max_prod_fast_int_as, narrow_range};
+#[cfg(feature = "alloc")]
+pub use index::max_prod_fast_real_as;
+pub use int::MaxProdInt;
+#[cfg(feature = "alloc")]
+pub use log_domain::{
+    max_geomean_fast_real,
+    max_prod_fast_real_log,
+    max_prod_fast_real_max_len,
+    max_prod_fast_real_subnormal_safe,
+    max_prod_fast_real_with_threshold,
+    max_prod_log_int,
+    MaxProdRealTracker,
+};
+#[cfg(feature = "alloc")]
+pub use matrix::max_prod_submatrix_real;
+
+pub use max_prod::{
+    max_prod_brute_force,
+    max_prod_brute_force_with_value,
+    max_prod_brute_force_non_empty,
+    max_prod_brute_force_result,
+    max_prod_brute_force_span,
+    max_prod_brute_force_with_progress,
+    max_prod_brute_force_unchecked,
+    max_prod_brute_force_checked,
+    max_prod_brute_force_improved,
+    max_prod_brute_force_improved_with_value,
+    max_prod_brute_force_improved_non_empty,
+    max_prod_brute_force_improved_result,
+    max_prod_brute_force_improved_span,
+    max_prod_brute_force_improved_checked,
+    max_prod_fast_int,
+    max_prod_fast_int_with_value,
+    max_prod_fast_int_non_empty,
+    max_prod_fast_int_bounded,
+    max_prod_fast_int_result,
+    max_prod_fast_int_span,
+    max_prod_fast_int_checked,
+    max_prod_fast_int_saturating,
+    max_prod_fast_int_signed,
+    max_prod_fast_int_signed_with_value,
+    max_prod_fast_int_signed_result,
+    max_abs_prod_fast_int,
+    max_abs_prod_fast_int_with_value,
+    max_prod_subslice_int,
+    checked_prod,
+    prod,
+    try_max_prod_brute_force,
+    try_max_prod_brute_force_unchecked,
+    try_max_prod_brute_force_improved,
+    try_max_prod_fast_int,
+    try_max_prod_fast_int_positive,
+    try_max_prod_fast_int_signed,
+};
+
+#[cfg(feature = "ndarray")]
+pub use ndarray::max_prod_fast_real_ndarray;
+pub use non_empty::NonEmptySlice;
+#[cfg(feature = "rayon")]
+pub use par::{max_prod_brute_force_par, max_prod_fast_real_par, max_prod_fast_real_par_with_value, top_k_max_prod_batch};
+#[cfg(feature = "alloc")]
+pub use prefix::PrefixProduct;
+
+#[cfg(feature = "alloc")]
+pub use rational::{max_prod_fast_rational, max_prod_fast_rational_with_value};
+#[cfg(feature = "alloc")]
+pub use real::{
+    compress_dual,
+    compress_dual_with_cumulative,
+    max_prod_fast_real,
+    max_prod_fast_real_with_value,
+    max_prod_fast_real_non_empty,
+    max_prod_fast_real_result,
+    max_prod_fast_real_span,
+    max_prod_fast_real_explain,
+    max_prod_fast_real_opts,
+    max_prod_fast_real_epsilon,
+    max_prod_fast_real_inplace,
+    max_prod_kadane_real,
+    max_min_prod_real,
+    max_prod_subslice_real,
+    max_prod_elements_real,
+    min_prod_fast_real,
+    try_max_prod_fast_real,
+    Segment,
+};
+#[cfg(feature = "alloc")]
+pub use real_f64::{max_prod_fast_real_f64, max_prod_fast_real_f64_with_value};
+
+pub use result::MaxProdResult;
+pub use rle::max_prod_fast_int_rle;
+#[cfg(feature = "alloc")]
+pub use runs::positive_runs_int;
+pub use score::{max_prod_by_fast_int, product_score};
+#[cfg(feature = "alloc")]
+pub use segtree::MaxProdSegTree;
+#[cfg(feature = "simd")]
+pub use simd::max_prod_fast_real_simd;
+pub use span::Span;
+pub use threshold::first_prod_exceeding_int;
+pub use tie_break::{max_prod_fast_int_rightmost, max_prod_fast_int_with_tiebreak, TieBreak};
+#[cfg(feature = "alloc")]
+pub use top_k::top_k_max_prod_fast_int;
+pub use tracker::MaxProdIntTracker;
+pub use verify::verify_max_prod;
+#[cfg(feature = "wasm")]
+pub use wasm::max_prod_fast_real_js;
+pub use widen::{max_prod_fast_int_widening, max_prod_fast_int_widening_with_value, Widen};
+pub use window::{max_prod_fixed_len_int, window_products};
@@ -0,0 +1,649 @@
+use alloc::collections::VecDeque;
+use alloc::vec;
+
+use num::{Float, ToPrimitive};
+
+use crate::int::MaxProdInt;
+use crate::real::{combine_compressed, compress_dual_with, max_prod_fast_real, nan_as_zero, widest_inf_run, Segment};
+
+/// Like [`max_prod_fast_real`](crate::max_prod_fast_real) but compares sums of `ln(x)`
+/// instead of products.
+///
+/// This can't underflow or overflow on long arrays, zeros map to `-inf` and act as
+/// separators, just like `NaN`. Runs in O(n) as a maximum sum search over the logarithms.
+/// On ties the range with the smallest start wins, like in the brute force.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_log;
+///
+/// assert_eq!(max_prod_fast_real_log(&[0.5, 2.0, 3.0, 0.1]), (1, 2));
+/// assert_eq!(max_prod_fast_real_log(&[2.0, 0.0, 3.0]), (2, 2));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_log<T: Float>(arr: &[T]) -> (usize, usize) {
+    let mut tracker = MaxProdRealTracker::new();
+    tracker.extend(arr.iter().copied());
+
+    tracker.best()
+}
+
+/// Like [`max_prod_fast_real_with_threshold`] with the smallest normal number of `T` as the
+/// threshold, so it switches to the log domain as soon as a product becomes subnormal.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_subnormal_safe;
+///
+/// // 1e-200 * 1e-200 underflows, 3 * 1e-400 * 5 would still be tiny
+/// assert_eq!(max_prod_fast_real_subnormal_safe(&[3.0, 1e-200, 1e-200, 5.0]), (3, 3));
+/// assert_eq!(max_prod_fast_real_subnormal_safe(&[0.5, 2.0, 0.0, 3.0, 0.9, 4.0]), (3, 5));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_subnormal_safe<T: Float>(arr: &[T]) -> (usize, usize) {
+    max_prod_fast_real_with_threshold(arr, T::min_positive_value())
+}
+
+/// Like [`max_prod_fast_real`](crate::max_prod_fast_real), but if the product of a run of
+/// numbers smaller than one drops below `threshold` the result of
+/// [`max_prod_fast_real_log`] is returned instead.
+///
+/// Products that become subnormal lose precision and eventually underflow to zero, sums of
+/// logarithms don't. A run of genuine zeros doesn't switch, and a threshold of zero never
+/// does.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_with_threshold;
+///
+/// // 2 * 0.25 * 3 = 1.5 loses against the 3 alone, with or without switching
+/// assert_eq!(max_prod_fast_real_with_threshold(&[0.5, 2.0, 0.25, 3.0], 0.1), (3, 3));
+/// assert_eq!(max_prod_fast_real_with_threshold(&[0.5, 2.0, 0.25, 3.0], 0.5), (3, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_with_threshold<T: Float>(arr: &[T], threshold: T) -> (usize, usize) {
+    // without runs of numbers smaller than one nothing can become subnormal
+    if arr.is_empty() || widest_inf_run(arr).is_some() || arr.iter().all(|&x| x >= T::one()) {
+        return max_prod_fast_real(arr);
+    }
+
+    let compressed = compress_dual_with(arr, nan_as_zero).unwrap();
+
+    if below_threshold(arr, &compressed, threshold) {
+        return max_prod_fast_real_log(arr);
+    }
+
+    let (_, i, j) = combine_compressed(compressed).unwrap();
+    (i, j)
+}
+
+/// Whether the product of a small segment is below `threshold` without containing a zero.
+fn below_threshold<T: Float>(arr: &[T], compressed: &[Segment<T>], threshold: T) -> bool {
+    // the small segments are every second one, if there is only one segment it is a single
+    // element and not a product
+    compressed.iter().skip(1).step_by(2).any(|&(p, i, j)| {
+        p < threshold && (p > T::zero() || arr[i..=j].iter().all(|&x| nan_as_zero(x) != T::zero()))
+    })
+}
+
+/// Streaming version of [`max_prod_fast_real_log`].
+///
+/// Values are fed one at a time with [`push`](Self::push), only the running sums of
+/// `ln(x)` are kept, so long runs of small values can't underflow like a product would.
+/// Feeding all elements of an array and then calling [`best`](Self::best) gives the same
+/// result as calling `max_prod_fast_real_log` on it.
+///
+/// ```
+/// use max_prod::MaxProdRealTracker;
+///
+/// let mut t = MaxProdRealTracker::new();
+/// t.extend([0.5, 2.0, 3.0, 0.1]);
+/// assert_eq!(t.best(), (1, 2));
+/// assert!((t.best_log() - 6.0f64.ln()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxProdRealTracker<T> {
+    max_sum: T,
+    max: (usize, usize),
+    current_start: usize,
+    current_sum: T,
+    n: usize,
+}
+
+impl<T: Float> MaxProdRealTracker<T> {
+    /// An empty tracker, its best range is `(0, 0)` until a value is pushed.
+    pub fn new() -> Self {
+        MaxProdRealTracker {
+            max_sum: T::neg_infinity(),
+            max: (0, 0),
+            current_start: 0,
+            current_sum: T::neg_infinity(),
+            n: 0,
+        }
+    }
+
+    /// Appends `value` at index [`len`](Self::len) and updates the best range. Values must
+    /// not be negative, a zero has a logarithm of `-inf` and ends every range before it.
+    pub fn push(&mut self, value: T) {
+        self.push_ln(value.ln());
+    }
+
+    /// Like [`push`](Self::push) with `ln(value)` computed by the caller.
+    pub(crate) fn push_ln(&mut self, l: T) {
+        let i = self.n;
+        self.n += 1;
+
+        // a negative sum is dropped and a new range starts here, clamping the sum with max
+        // keeps at least its update free of a branch on the unpredictable sign
+        let extend = self.current_sum >= T::zero();
+        if !extend {
+            self.current_start = i;
+        }
+        self.current_sum = self.current_sum.max(T::zero()) + l;
+
+        if self.current_sum > self.max_sum || (self.current_sum == self.max_sum && self.current_start < self.max.0) {
+            self.max_sum = self.current_sum;
+            self.max = (self.current_start, i);
+        }
+    }
+
+    /// The best range of all values pushed so far.
+    pub fn best(&self) -> (usize, usize) {
+        self.max
+    }
+
+    /// `ln` of the product of [`best`](Self::best).
+    pub fn best_log(&self) -> T {
+        self.max_sum
+    }
+
+    /// Number of values pushed so far.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Whether no value has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+impl<T: Float> Default for MaxProdRealTracker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Extend<T> for MaxProdRealTracker<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Like [`max_prod_fast_int_with_value`](crate::max_prod_fast_int_with_value) but returns
+/// `ln` of the maximum product, computed as a sum of `ln(x)` so the product itself is never
+/// materialized and can't overflow.
+///
+/// If there is no non-zero element the logarithm is `-inf`. Sums of logarithms are rounded,
+/// so when two ranges have the same product the returned range can differ from the one of
+/// `max_prod_fast_int`.
+///
+/// ```
+/// use max_prod::max_prod_log_int;
+///
+/// let (log, i, j) = max_prod_log_int([2u32, 0, 3, 4]);
+/// assert_eq!((i, j), (2, 3));
+/// assert!((log - 12f64.ln()).abs() < 1e-12);
+/// ```
+#[must_use]
+pub fn max_prod_log_int<T: MaxProdInt + ToPrimitive>(arr: impl AsRef<[T]>) -> (f64, usize, usize) {
+    let arr = arr.as_ref();
+    let mut best: Option<(f64, usize, usize)> = None;
+
+    let mut current = (0, 0);
+    let mut current_log = f64::NEG_INFINITY;
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x != T::zero() {
+            if current_log == f64::NEG_INFINITY {
+                current_log = 0.0;
+                current.0 = i;
+            }
+            current_log += Float::ln(x.to_f64().unwrap());
+            current.1 = i;
+        } else {
+            current = (i, i);
+            current_log = f64::NEG_INFINITY;
+        }
+
+        // not `consider`, a product of one has a logarithm of zero
+        if current_log > f64::NEG_INFINITY && best.is_none_or(|(l, _, _)| current_log > l) {
+            best = Some((current_log, current.0, current.1));
+        }
+    }
+
+    best.unwrap_or((f64::NEG_INFINITY, 0, 0))
+}
+
+/// Finds the range with the maximum product among ranges of at most `max_len` elements.
+///
+/// Works on prefix sums of `ln(x)` with a monotonic deque holding the candidate starts
+/// of the sliding window, so it runs in O(n). Zeros and `NaN` reset the window.
+/// Returns `None` if `max_len == 0` or the array is empty.
+///
+/// The elements must not be negative: `ln(x)` of a negative element is `NaN`, which makes
+/// every prefix sum after it `NaN` up to the next zero, and the returned range is then
+/// meaningless.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_max_len;
+///
+/// // [0 .. 3] has the largest product 12, but only two elements are allowed
+/// assert_eq!(max_prod_fast_real_max_len(&[2.0, 3.0, 0.5, 4.0], 2), Some((0, 1)));
+/// assert_eq!(max_prod_fast_real_max_len(&[2.0, 3.0, 0.5, 4.0], 4), Some((0, 3)));
+/// assert_eq!(max_prod_fast_real_max_len(&[2.0, 3.0], 0), None);
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_max_len<T: Float>(arr: &[T], max_len: usize) -> Option<(usize, usize)> {
+    let n = arr.len();
+
+    if max_len == 0 || n == 0 {
+        return None;
+    }
+
+    let mut max_sum = T::neg_infinity();
+    let mut max = (0, 0);
+
+    // prefix[k] = sum of ln(x) from the last separator up to but excluding k
+    let mut prefix = vec![T::zero(); n + 1];
+    // candidate starts in the window, their prefix sums are increasing
+    let mut starts: VecDeque<usize> = VecDeque::new();
+
+    for j in 0..n {
+        if arr[j] == T::zero() || arr[j].is_nan() {
+            starts.clear();
+            prefix[j + 1] = T::zero();
+            continue;
+        }
+
+        // keep equal prefix sums so the smaller start wins on ties
+        while starts.back().is_some_and(|&k| prefix[k] > prefix[j]) {
+            starts.pop_back();
+        }
+        starts.push_back(j);
+
+        prefix[j + 1] = prefix[j] + arr[j].ln();
+
+        while starts.front().is_some_and(|&k| k + max_len <= j) {
+            starts.pop_front();
+        }
+
+        let i = *starts.front().unwrap();
+        let sum = prefix[j + 1] - prefix[i];
+
+        if sum > max_sum || (sum == max_sum && i < max.0) {
+            max_sum = sum;
+            max = (i, j);
+        }
+    }
+
+    Some(max)
+}
+
+/// Finds the range of at least `min_len` elements with the largest geometric mean and
+/// returns the mean together with the range.
+///
+/// Unlike the product the mean doesn't grow with the length of a range, so without a
+/// minimum length the best range would be the largest single element. An element that
+/// isn't positive, and `NaN`, makes the mean of every range containing it zero. On ties the
+/// range with the smallest start and then the smallest end wins, if every range has a mean
+/// of zero that is `(0, min_len - 1)`. A `min_len` of zero counts as one.
+///
+/// A range of `2 * min_len` or more elements splits into two ranges of at least `min_len`,
+/// one with a mean at least as large, so only lengths below `2 * min_len` are compared as
+/// sliding windows over prefix sums of `ln(x)`, which takes O(n * min_len).
+/// Returns `None` if the array is shorter than `min_len`.
+///
+/// ```
+/// use max_prod::max_geomean_fast_real;
+///
+/// let (mean, i, j) = max_geomean_fast_real(&[9.0f64, 0.1, 4.0, 4.0, 1.0], 2).unwrap();
+/// assert_eq!((i, j), (2, 3));
+/// assert!((mean - 4.0).abs() < 1e-12);
+/// ```
+#[must_use]
+pub fn max_geomean_fast_real<T: Float>(arr: &[T], min_len: usize) -> Option<(T, usize, usize)> {
+    let min_len = min_len.max(1);
+    let n = arr.len();
+
+    if n < min_len {
+        return None;
+    }
+
+    // prefix[k] = sum of ln(x) of the positive elements before k,
+    // bad[k] = number of elements before k that aren't positive
+    let mut prefix = vec![T::zero(); n + 1];
+    let mut bad = vec![0; n + 1];
+    for (k, &x) in arr.iter().enumerate() {
+        let positive = x > T::zero();
+        prefix[k + 1] = prefix[k] + if positive { x.ln() } else { T::zero() };
+        bad[k + 1] = bad[k] + usize::from(!positive);
+    }
+
+    let mut max_mean = T::neg_infinity();
+    let mut max = (0, min_len - 1);
+
+    for i in 0..=(n - min_len) {
+        for j in (i + min_len - 1)..n.min(i + 2 * min_len - 1) {
+            if bad[j + 1] != bad[i] {
+                continue;
+            }
+
+            let mean = (prefix[j + 1] - prefix[i]) / T::from(j - i + 1).unwrap();
+
+            if mean > max_mean {
+                max_mean = mean;
+                max = (i, j);
+            }
+        }
+    }
+
+    Some((max_mean.exp(), max.0, max.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::{max_prod_brute_force_improved, max_prod_fast_int, prod};
+    use crate::real::max_prod_fast_real;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_log_basic() {
+        let arr = vec![0.1, 0.5, 13.0, 2.0, 0.1, 4.0, 6.0, 7.0, 8.0, 0.1, 0.2];
+        assert_eq!(max_prod_fast_real_log(&arr), max_prod_brute_force_improved(&arr));
+
+        let arr = vec![0.0, 0.5, 0.0, 0.25];
+        assert_eq!(max_prod_fast_real_log(&arr), (1, 1));
+
+        let arr = vec![0.5, f64::NAN, 3.0, 4.0];
+        assert_eq!(max_prod_fast_real_log(&arr), (2, 3));
+
+        let arr = vec![0.0, 0.0];
+        assert_eq!(max_prod_fast_real_log(&arr), (0, 0));
+    }
+
+    #[test]
+    fn test_random_log() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(100).collect();
+            assert_eq!(max_prod_fast_real_log(&a), max_prod_brute_force_improved(&a));
+        }
+    }
+
+    #[test]
+    fn test_log_underflow() {
+        let mut rng = test_rng();
+        let a: Vec<f64> = Uniform::new(0.0, 0.5).sample_iter(&mut rng).take(10_000).collect();
+        assert_eq!(max_prod_fast_real_log(&a), max_prod_brute_force_improved(&a));
+        assert_eq!(max_prod_fast_real_log(&a), max_prod_fast_real(&a));
+    }
+
+    #[test]
+    fn test_log_int() {
+        let (l, i, j) = max_prod_log_int([2u64, 0, 3, 4, 1, 0, 5]);
+        assert_eq!((i, j), (2, 3));
+        assert!((l.exp() - 12.0).abs() < 1e-9);
+
+        assert_eq!(max_prod_log_int([0u64, 0]), (f64::NEG_INFINITY, 0, 0));
+        assert_eq!(max_prod_log_int::<u64>([]), (f64::NEG_INFINITY, 0, 0));
+        assert_eq!(max_prod_log_int([0u64, 1, 1]), (0.0, 1, 1));
+
+        // 10^100 doesn't fit into any integer type
+        let (l, i, j) = max_prod_log_int([10u64; 100]);
+        assert_eq!((i, j), (0, 99));
+        assert!((l - 100.0 * 10f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_random_log_int() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 9).sample_iter(&mut rng).take(12).collect();
+            let (l, i, j) = max_prod_log_int(&a);
+
+            let (k, m) = max_prod_fast_int(&a);
+            let p = prod(&a, k, m) as f64;
+            assert!((l.exp() - p).abs() <= p * 1e-9, "{a:?}");
+            assert!((prod(&a, i, j) as f64 - p).abs() <= p * 1e-9, "{a:?}");
+        }
+    }
+
+    #[test]
+    fn test_real_tracker_basic() {
+        let mut t = MaxProdRealTracker::new();
+        assert!(t.is_empty());
+        assert_eq!(t.best(), (0, 0));
+
+        t.push(0.5);
+        assert_eq!(t.best(), (0, 0));
+        t.push(3.0);
+        assert_eq!(t.best(), (1, 1));
+        t.push(0.5);
+        t.push(4.0);
+        assert_eq!(t.best(), (1, 3));
+        assert!((t.best_log() - 6f64.ln()).abs() < 1e-12);
+        assert_eq!(t.len(), 4);
+    }
+
+    #[test]
+    fn test_random_real_tracker() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(30).collect();
+            let mut t = MaxProdRealTracker::default();
+
+            for (k, &x) in a.iter().enumerate() {
+                t.push(x);
+                assert_eq!(t.best(), max_prod_brute_force_improved(&a[..=k]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_real_tracker_underflow() {
+        // 0.9^50_000 underflows to zero and 1e5^1000 overflows, but in log space the
+        // whole array wins
+        let mut t = MaxProdRealTracker::new();
+        t.extend(core::iter::repeat_n(1e5, 1000));
+        t.extend(core::iter::repeat_n(0.9, 50_000));
+        assert_eq!(t.best(), (0, 999));
+        t.extend(core::iter::repeat_n(1e5, 1000));
+        assert_eq!(t.best(), (0, 51_999));
+
+        let a: Vec<f64> = core::iter::repeat_n(1e5, 1000)
+            .chain(core::iter::repeat_n(0.9, 50_000))
+            .chain(core::iter::repeat_n(1e5, 1000))
+            .collect();
+        assert_eq!(t.best(), max_prod_fast_real_log(&a));
+        assert_eq!(0.9f64.powi(50_000), 0.0);
+    }
+
+    #[test]
+    fn test_subnormal_run() {
+        // 0.5^1060 is subnormal
+        let mut a = vec![3.0];
+        a.extend([0.5; 1060]);
+        a.push(2.0);
+
+        let compressed = compress_dual_with(&a, nan_as_zero).unwrap();
+        assert!(compressed[1].0 < f64::MIN_POSITIVE && compressed[1].0 > 0.0);
+        assert!(below_threshold(&a, &compressed, f64::MIN_POSITIVE));
+        assert!(!below_threshold(&a, &compressed, 0.0));
+        assert_eq!(max_prod_fast_real_subnormal_safe(&a), max_prod_fast_real_log(&a));
+        assert_eq!(max_prod_fast_real_subnormal_safe(&a), (0, 0));
+
+        // 0.5^1100 underflows to zero, which is only treated like a zero element if it is one
+        let mut a = vec![3.0];
+        a.extend([0.5; 1100]);
+        a.extend([4.0, 0.0, 0.5, 5.0]);
+        let compressed = compress_dual_with(&a, nan_as_zero).unwrap();
+        assert_eq!(compressed[1].0, 0.0);
+        assert!(below_threshold(&a, &compressed, f64::MIN_POSITIVE));
+        assert!(!below_threshold(&a, &compressed[2..], f64::MIN_POSITIVE));
+        assert_eq!(max_prod_fast_real_subnormal_safe(&a), (1104, 1104));
+    }
+
+    #[test]
+    fn test_random_threshold() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(50).collect();
+            assert_eq!(max_prod_fast_real_subnormal_safe(&a), max_prod_fast_real(&a));
+
+            // long runs of tiny numbers
+            let mut a = a;
+            for k in (0..50).step_by(10) {
+                a.splice(k..k, Uniform::new(0.0, 0.01).sample_iter(&mut rng).take(200));
+            }
+            assert_eq!(max_prod_fast_real_subnormal_safe(&a), max_prod_fast_real_log(&a));
+        }
+    }
+
+    fn max_prod_brute_force_max_len(arr: &[f64], max_len: usize) -> (usize, usize) {
+        let mut max_prod = 0.0;
+        let mut max = (0, 0);
+        let n = arr.len();
+
+        for i in 0..n {
+            let mut prod = 1.0;
+            for (j, &x) in arr.iter().enumerate().take(i + max_len).skip(i) {
+                prod *= x;
+
+                if prod > max_prod {
+                    max_prod = prod;
+                    max = (i, j);
+                }
+            }
+        }
+
+        max
+    }
+
+    #[test]
+    fn test_max_len_basic() {
+        let arr = vec![0.5, 2.0, 3.0, 0.9, 4.0, 0.1];
+        assert_eq!(max_prod_fast_real_max_len(&arr, 0), None);
+        assert_eq!(max_prod_fast_real_max_len(&arr, 1), Some((4, 4)));
+        assert_eq!(max_prod_fast_real_max_len(&arr, 2), Some((1, 2)));
+        assert_eq!(max_prod_fast_real_max_len(&arr, 4), Some((1, 4)));
+        assert_eq!(max_prod_fast_real_max_len(&arr, 100), Some(max_prod_brute_force_improved(&arr)));
+
+        let arr: Vec<f64> = vec![];
+        assert_eq!(max_prod_fast_real_max_len(&arr, 3), None);
+
+        let arr = vec![0.0, 0.0];
+        assert_eq!(max_prod_fast_real_max_len(&arr, 3), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_random_max_len() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..40);
+            let max_len = rng.gen_range(1..n + 3);
+            let mut a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            if rng.gen_bool(0.3) {
+                a[rng.gen_range(0..n)] = 0.0;
+            }
+
+            assert_eq!(max_prod_fast_real_max_len(&a, max_len), Some(max_prod_brute_force_max_len(&a, max_len)), "a = {:?} max_len = {}", a, max_len);
+
+            if max_len >= n {
+                assert_eq!(max_prod_fast_real_max_len(&a, max_len), Some(max_prod_fast_real_log(&a)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_over_and_underflow() {
+        // the runs of large values overflow to inf and the run of small values
+        // underflows to 0, yet the whole array has the largest product
+        let mut a = vec![1e5; 101];
+        a.extend([1e-5; 100]);
+        a.extend([1e5; 101]);
+        assert_eq!(max_prod_fast_real_log(&a), (0, 301));
+    }
+
+    fn max_geomean_brute_force(arr: &[f64], min_len: usize) -> Option<(f64, usize, usize)> {
+        let n = arr.len();
+        let mut max: Option<(f64, usize, usize)> = None;
+
+        for i in 0..n {
+            for j in (i + min_len.max(1) - 1)..n {
+                let window = &arr[i..=j];
+                let mean = if window.iter().all(|&x| x > 0.0) {
+                    (window.iter().map(|x| x.ln()).sum::<f64>() / window.len() as f64).exp()
+                } else {
+                    0.0
+                };
+
+                if max.is_none_or(|(m, _, _)| mean > m) {
+                    max = Some((mean, i, j));
+                }
+            }
+        }
+
+        max
+    }
+
+    #[test]
+    fn test_geomean_basic() {
+        let arr = vec![9.0, 0.1, 4.0, 4.0, 1.0];
+        for (min_len, range, expected) in [(0, (0, 0), 9.0), (1, (0, 0), 9.0), (2, (2, 3), 4.0), (4, (0, 3), 14.4f64.powf(0.25))] {
+            let (mean, i, j) = max_geomean_fast_real(&arr, min_len).unwrap();
+            assert_eq!((i, j), range);
+            assert!((mean - expected).abs() < 1e-12);
+        }
+        assert_eq!(max_geomean_fast_real(&arr, 6), None);
+
+        // every range contains a zero or a negative number
+        let arr = vec![2.0, 0.0, 3.0, -1.0, 5.0];
+        assert_eq!(max_geomean_fast_real(&arr, 2), Some((0.0, 0, 1)));
+
+        let arr = vec![2.0, f64::NAN, 3.0, 3.0];
+        assert_eq!(max_geomean_fast_real(&arr, 2).map(|(_, i, j)| (i, j)), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_random_geomean() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..30);
+            let min_len = rng.gen_range(0..n + 2);
+            let mut a: Vec<f64> = Uniform::new(0.0, 4.0).sample_iter(&mut rng).take(n).collect();
+            if rng.gen_bool(0.3) {
+                a[rng.gen_range(0..n)] = 0.0;
+            }
+
+            let got = max_geomean_fast_real(&a, min_len);
+            let expected = max_geomean_brute_force(&a, min_len);
+
+            match (got, expected) {
+                (Some((m, i, j)), Some((e, _, _))) => {
+                    assert!((m - e).abs() <= 1e-9 * e, "a = {:?} min_len = {} got {} expected {}", a, min_len, m, e);
+                    assert!(j - i + 1 >= min_len.max(1));
+
+                    let (own, _, _) = max_geomean_brute_force(&a[i..=j], j - i + 1).unwrap();
+                    assert!((m - own).abs() <= 1e-9 * own);
+                }
+                (got, expected) => assert_eq!(got, expected, "a = {:?} min_len = {}", a, min_len),
+            }
+        }
+    }
+}
@@ -1,317 +1,208 @@
-use std::fmt::{Debug, Display};
-use std::io::{stdout, Write};
-
-use num::{Integer, Unsigned};
-use num::traits::Num;
-use num::traits::real::Real;
-use rand::{Rng, thread_rng};
-use rand::distributions::uniform::Uniform;
-use rand::distributions::Distribution;
-
-
-fn max_prod_brute_force<T: Num + Copy + PartialOrd + Display>(arr: &[T]) -> (usize, usize) {
-    let mut max_prod = T::zero();
-    let mut max = (0, 0);
-    let n = arr.len();
-
-    for i in 0..n {
-        for j in i..n {
-            let mut prod = T::one();
-
-            for k in i..=j {
-                prod = prod * arr[k];
+use std::env;
+use std::fs;
+use std::io::{stdin, Read};
+use std::process::ExitCode;
+
+use max_prod::{
+    max_prod_brute_force_improved_checked,
+    max_prod_brute_force_improved_with_value,
+    max_prod_fast_int_checked,
+    max_prod_fast_real_with_value,
+    max_prod_kadane_real,
+    prod,
+    OverflowError,
+};
+
+const USAGE: &str = "usage: max-prod [--algo brute|fast-int|fast-real] [--csv <path> [--column N] | --binary] < numbers";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algo {
+    Brute,
+    FastInt,
+    FastReal,
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    algo: Option<Algo>,
+    csv: Option<String>,
+    column: usize,
+    binary: bool,
+}
+
+#[derive(Debug)]
+enum Numbers {
+    Int(Vec<u64>),
+    Real(Vec<f64>),
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--algo" => {
+                let name = args.next().ok_or("--algo needs a value")?;
+                parsed.algo = Some(match name.as_str() {
+                    "brute" => Algo::Brute,
+                    "fast-int" => Algo::FastInt,
+                    "fast-real" => Algo::FastReal,
+                    _ => return Err(format!("unknown algorithm '{name}'")),
+                });
             }
-
-            if prod > max_prod {
-                max_prod = prod;
-                max = (i, j);
+            "--csv" => parsed.csv = Some(args.next().ok_or("--csv needs a path")?),
+            "--column" => {
+                let n = args.next().ok_or("--column needs a value")?;
+                parsed.column = n.parse().map_err(|_| format!("invalid column '{n}'"))?;
             }
+            "--binary" => parsed.binary = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => return Err(format!("unknown argument '{arg}'\n{USAGE}")),
         }
     }
 
-    assert!(max.0 <= max.1);
-    max
-}
-
-fn max_prod_brute_force_improved<T: Num + Copy + PartialOrd + Display>(arr: &[T]) -> (usize, usize) {
-    let mut max_prod = T::zero();
-    let mut max = (0, 0);
-    let n = arr.len();
-
-    for i in 0..n {
-        let mut prod = T::one();
-        for j in i..n {
-            prod = prod * arr[j];
-
-            if prod > max_prod {
-                max_prod = prod;
-                max = (i, j);
-            }
-        }
+    if parsed.binary && parsed.csv.is_some() {
+        return Err("--binary and --csv can't be combined".to_string());
     }
 
-    assert!(max.0 <= max.1);
-    max
+    Ok(parsed)
 }
 
-fn max_prod_fast_int<T: Num + Integer + Copy + Unsigned>(arr: &[T]) -> (usize, usize) {
-    let mut max_prod = T::zero();
-    let mut max = (0, 0);
-
-    let n = arr.len();
-    let mut current = (0, 0);
-    let mut current_prod = T::zero();
-
-    for i in 0..n {
-        if arr[i] != T::zero() {
-            if current_prod == T::zero() {
-                current_prod = T::one();
-                current.0 = i;
-            }
-            current_prod = current_prod * arr[i];
-            current.1 = i;
-        } else {
-            current = (i, i);
-            current_prod = T::zero();
-        }
-
-        if current_prod > max_prod {
-            max = current;
-            max_prod = current_prod;
-        }
+/// Numbers are integers if every token parses as a non-negative integer, otherwise reals.
+fn parse_numbers(tokens: &[&str]) -> Result<Numbers, String> {
+    if let Ok(ints) = tokens.iter().map(|t| t.parse()).collect::<Result<Vec<u64>, _>>() {
+        return Ok(Numbers::Int(ints));
     }
 
-    assert!(max.0 <= max.1);
-    max
+    tokens.iter()
+        .map(|t| t.parse().map_err(|_| format!("'{t}' is not a number")))
+        .collect::<Result<Vec<f64>, _>>()
+        .map(Numbers::Real)
 }
 
-fn compress_dual<T: Real + Copy>(arr: &[T]) -> Vec<(T, usize, usize)> {
-    let n = arr.len();
-    let mut compressed = Vec::new();
-
-    let mut tmp_prod = T::one();
-    let mut start = 0;
+/// Every 8 bytes are one little-endian `u64`.
+fn parse_binary(bytes: &[u8]) -> Result<Vec<u64>, String> {
+    let chunks = bytes.chunks_exact(8);
 
-    let mut tmp_max = T::zero();
-    let mut tmp_max_idx = 0;
-
-    while start < n && arr[start] < T::one()  {
-        if arr[start] > tmp_max {
-            tmp_max = arr[start];
-            tmp_max_idx = start;
-        }
-
-        start += 1;
+    if !chunks.remainder().is_empty() {
+        return Err(format!("truncated input, {} trailing bytes after {} numbers", chunks.remainder().len(), chunks.len()));
     }
 
-    if start == n {
-        return vec![(tmp_max, tmp_max_idx, tmp_max_idx)];
-    }
+    Ok(chunks.map(|b| u64::from_le_bytes(b.try_into().unwrap())).collect())
+}
 
-    let mut smaller = arr[start] < T::one(); // true = compressing numbers smaller than one
+/// Reads column `column` of every row, rows that don't have a number there are skipped
+/// with a warning. Returns the tokens together with the row each one came from.
+fn read_csv_column(input: &str, column: usize) -> (Vec<&str>, Vec<usize>) {
+    let mut tokens = Vec::new();
+    let mut rows = Vec::new();
 
-    for i in start..n {
-        if smaller {
-            if arr[i] < T::one() {
-                tmp_prod = tmp_prod * arr[i];
-            } else {
-                compressed.push((tmp_prod, start, i - 1));
-                smaller = false;
-                tmp_prod = arr[i];
-                start = i;
-            }
-        } else {
-            if arr[i] > T::one() {
-                tmp_prod = tmp_prod * arr[i];
-            } else {
-                compressed.push((tmp_prod, start, i - 1));
-                smaller = true;
-                tmp_prod = arr[i];
-                start = i;
+    for (row, line) in input.lines().enumerate() {
+        match line.split(',').nth(column).map(str::trim) {
+            Some(t) if t.parse::<f64>().is_ok() => {
+                tokens.push(t);
+                rows.push(row);
             }
+            _ => eprintln!("warning: skipping row {row}, no number in column {column}"),
         }
     }
 
-    if tmp_prod > T::one() {
-        compressed.push((tmp_prod, start, n - 1));
-    }
-
-    assert!(!compressed.is_empty());
-
-    compressed
+    (tokens, rows)
 }
 
-fn max_prod_fast_real<T: Real + Copy + Debug>(arr: &[T]) -> (usize, usize) {
-    let mut compressed = compress_dual(arr);
-    //println!("compressed = {:?}", compressed);
-    let mut current_max = compressed[0];
-
-    while compressed.len() >= 3 {
-        //println!("compressed = {:?}", compressed);
-        let a = compressed.pop().unwrap(); // arr[n - 1]
-        let b = compressed.pop().unwrap(); // arr[n - 2]
-        let c = compressed.pop().unwrap(); // arr[n - 3]
-
-        //println!("a = {:?}  b = {:?}  c = {:?}", a, b, c);
-
-        let combined = (a.0 * b.0 * c.0, c.1, a.2);
-        //println!("combined = {:?}", combined);
-
-        if combined.0 > c.0 {
-            compressed.push(combined);
-        } else {
-            compressed.push(c);
+/// The range and its product, or with a warning the range whose product overflowed first
+/// instead of a wrapped value.
+fn int_result(arr: &[u64], result: Result<(usize, usize), OverflowError>) -> (usize, usize, String) {
+    match result {
+        Ok((i, j)) => (i, j, prod(arr, i, j).to_string()),
+        Err(OverflowError { start, end }) => {
+            eprintln!("warning: the product of [{start} .. {end}] overflows u64");
+            (start, end, "overflow".to_string())
         }
+    }
+}
 
-        if combined.0 > current_max.0 {
-            current_max = combined;
-            //println!("new max (com) = {:?}", current_max);
+fn run(numbers: Numbers, algo: Option<Algo>) -> Result<(usize, usize, String), String> {
+    match (numbers, algo) {
+        (Numbers::Int(arr), None | Some(Algo::FastInt)) => {
+            Ok(int_result(&arr, max_prod_fast_int_checked(&arr)))
         }
-        if a.0 > current_max.0 {
-            current_max = a;
-            //println!("new max ( a ) = {:?}", current_max);
+        (Numbers::Int(arr), Some(Algo::Brute)) => {
+            Ok(int_result(&arr, max_prod_brute_force_improved_checked(&arr)))
         }
-        if c.0 > current_max.0 {
-            current_max = c;
-            //println!("new max ( c ) = {:?}", current_max);
+        (Numbers::Int(arr), Some(Algo::FastReal)) => {
+            run(Numbers::Real(arr.into_iter().map(|x| x as f64).collect()), Some(Algo::FastReal))
+        }
+        (Numbers::Real(_), Some(Algo::FastInt)) => {
+            Err("fast-int needs non-negative integers".to_string())
+        }
+        (Numbers::Real(arr), Some(Algo::Brute)) => {
+            let (p, i, j) = max_prod_brute_force_improved_with_value(&arr);
+            Ok((i, j, p.to_string()))
+        }
+        (Numbers::Real(arr), None) if arr.iter().any(|&x| x < 0.0) => {
+            let (i, j) = max_prod_kadane_real(&arr);
+            Ok((i, j, prod(&arr, i, j).to_string()))
+        }
+        (Numbers::Real(arr), None | Some(Algo::FastReal)) => {
+            let (p, i, j) = max_prod_fast_real_with_value(&arr);
+            Ok((i, j, p.to_string()))
         }
     }
-
-    //println!("final = {:?}", current_max);
-
-    (current_max.1, current_max.2)
-}
-
-fn prod<T: Num + Copy>(arr: &[T], i: usize, j: usize) -> T {
-    let mut prod = T::one();
-    for k in i..=j {
-        prod = prod * arr[k];
-    }
-
-    prod
 }
 
-fn main() {
-    let arr: Vec<f64> = vec![0.7677789417518834, 0.8933695534913264, 0.3914341615624717, 0.7672288709480366, 0.20364132732776996];
+fn main() -> ExitCode {
+    let result = parse_args(env::args().skip(1)).and_then(|args| {
+        if args.binary {
+            let mut bytes = Vec::new();
+            stdin().read_to_end(&mut bytes).map_err(|e| e.to_string())?;
 
-    println!("F = {:?}", arr);
-    let (i, j) = max_prod_fast_real(&arr[..]);
-    stdout().flush().unwrap();
-    println!("F[{i} .. {j}] = {}", prod(&arr, i, j));
+            let arr = parse_binary(&bytes)?;
+            if arr.is_empty() {
+                return Err("no numbers given".to_string());
+            }
 
-    let c = max_prod_brute_force_improved(&arr);
-    println!("F[{} .. {}] = {}", c.0, c.1, prod(&arr, c.0, c.1));
-}
+            let (i, j, p) = run(Numbers::Int(arr), args.algo)?;
+            return Ok(format!("[{i} .. {j}] = {p}"));
+        }
 
-#[test]
-fn test_real_basic() {
-    let mut arr = vec![0.1, 0.5, 13.0, 2.0, 0.1, 4.0, 6.0, 7.0, 8.0, 0.1, 0.2];
-    assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
-}
+        let input = match &args.csv {
+            Some(path) => fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?,
+            None => {
+                let mut input = String::new();
+                stdin().read_to_string(&mut input).map_err(|e| e.to_string())?;
+                input
+            }
+        };
 
-#[test]
-fn test_random_real() {
-    for _ in 0..1000 {
-        let mut a: Vec<f64> = thread_rng().sample_iter(Uniform::new(0.0, 2.0)).take(100).collect();
-        assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a));
-    }
-}
+        let (tokens, rows) = match &args.csv {
+            Some(_) => {
+                let (tokens, rows) = read_csv_column(&input, args.column);
+                (tokens, Some(rows))
+            }
+            None => (input.split_whitespace().collect(), None),
+        };
 
-#[test]
-fn test_random_real2() {
-    for i in 1..200 {
-        let mut a: Vec<f64> = thread_rng().sample_iter(Uniform::new(0.0, 2.0)).take(i / 2).collect();
-        println!("a = {:?}", a);
-        assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a));
-    }
-}
+        if tokens.is_empty() {
+            return Err("no numbers given".to_string());
+        }
 
-#[test]
-fn test_random_int() {
-    for _ in 0..500 {
-        let mut a: Vec<u128> = thread_rng().sample_iter(Uniform::new_inclusive(0, 10)).take(50).collect();
+        let (i, j, p) = run(parse_numbers(&tokens)?, args.algo)?;
 
-        let n = a.len();
-        a[Uniform::new(0, n).sample(&mut thread_rng())] = 0;
+        Ok(match rows {
+            Some(rows) => format!("[{i} .. {j}] = {p}\nrows {} .. {}", rows[i], rows[j]),
+            None => format!("[{i} .. {j}] = {p}"),
+        })
+    });
 
-        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    match result {
+        Ok(out) => {
+            println!("{out}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
     }
 }
-
-#[test]
-fn test_brute_force_basic() {
-    let a = vec![1u32, 2, 3, 4];
-    let max = max_prod_fast_int(&a);
-    assert_eq!(max, (0, 3));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
-}
-
-#[test]
-fn test_brute_force_basic2() {
-    let a = vec![0u32, 2, 3, 4];
-    let max = max_prod_fast_int(&a);
-    assert_eq!(max, (1, 3));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
-}
-
-#[test]
-fn test_brute_force_basic3() {
-    let a = vec![0, 1u32, 0, 0];
-    let max = max_prod_fast_int(&a);
-    assert_eq!(max, (1, 1));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
-}
-
-#[test]
-fn test_brute_force_basic4() {
-    let a = vec![0, 1u32, 0, 1];
-    let max = max_prod_fast_int(&a);
-    assert_eq!(max, (1, 1));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
-}
-
-#[test]
-fn test_brute_force_basic5() {
-    let a = vec![0, 1u32, 0, 7, 0, 3];
-    let max = max_prod_fast_int(&a);
-    assert_eq!(max, (3, 3));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
-}
-
-#[test]
-fn test_brute_force_basic6() {
-    let a = vec![4u32];
-    let max = max_prod_fast_int(&a);
-    assert_eq!(max, (0, 0));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
-}
-
-#[test]
-fn test_integer1() {
-    let a: Vec<u128> = vec![4, 9, 7, 3, 4, 8, 9, 2, 0, 3, 9, 6, 9, 2, 0, 5, 7, 2,
-                            5, 8, 9, 7, 1, 5, 2, 8, 3, 7, 5, 2, 7, 8, 3, 1, 5, 4, 6, 1, 2,
-                            5, 3, 2, 4, 4, 4, 3, 1, 9, 4, 7, 9, 4, 5, 7, 5, 5, 7, 5, 8, 9];
-    let max = max_prod_fast_int(&a);
-    assert_eq!(max, (15, 59));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
-    assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
-}
-
-
-#[test]
-fn test_real_brute_force_01() {
-    let farr: Vec<f32> = thread_rng().sample_iter(Uniform::new(0.0, 1.0)).take(20).collect();
-    println!("F = {:?}", farr);
-    let (i, j) = max_prod_brute_force(&farr[..]);
-    let p = prod(&farr, i, j);
-    let m = farr.into_iter().fold(0.0, |a, b| b.max(a));
-    println!("F[{i} .. {j}] = {} (max = {m})", p);
-    assert_eq!(p, m);
-}
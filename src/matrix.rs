@@ -0,0 +1,124 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::traits::real::Real;
+
+use crate::error::NonRectangularError;
+use crate::real::max_prod_fast_real_with_value;
+
+/// Finds the axis-aligned submatrix with the maximum product, returned as `(r0, c0, r1, c1)`.
+///
+/// For every band of rows `r0..=r1` the columns are reduced into a running product
+/// and [`max_prod_fast_real`](crate::max_prod_fast_real) is applied to the band, so this
+/// runs in O(rows^2 * cols).
+///
+/// ```
+/// use max_prod::max_prod_submatrix_real;
+///
+/// let m = vec![vec![2.0, 0.5], vec![3.0, 0.25]];
+/// assert_eq!(max_prod_submatrix_real(&m), Ok((0, 0, 1, 0)));
+///
+/// assert!(max_prod_submatrix_real(&[vec![1.0], vec![1.0, 2.0]]).is_err());
+/// ```
+pub fn max_prod_submatrix_real<T: Real + Copy>(matrix: &[Vec<T>]) -> Result<(usize, usize, usize, usize), NonRectangularError> {
+    let rows = matrix.len();
+    let cols = matrix.first().map_or(0, |r| r.len());
+
+    for (row, r) in matrix.iter().enumerate() {
+        if r.len() != cols {
+            return Err(NonRectangularError { row, expected: cols, found: r.len() });
+        }
+    }
+
+    if cols == 0 {
+        return Ok((0, 0, 0, 0));
+    }
+
+    let mut best: Option<(T, (usize, usize, usize, usize))> = None;
+
+    for r0 in 0..rows {
+        let mut band = vec![T::one(); cols];
+
+        for (r1, row) in matrix.iter().enumerate().skip(r0) {
+            for (b, &x) in band.iter_mut().zip(row) {
+                *b = *b * x;
+            }
+
+            let (p, c0, c1) = max_prod_fast_real_with_value(&band);
+            if p > T::zero() && best.is_none_or(|(b, _)| p > b) {
+                best = Some((p, (r0, c0, r1, c1)));
+            }
+        }
+    }
+
+    Ok(best.map_or((0, 0, 0, 0), |(_, max)| max))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn max_prod_submatrix_brute_force(matrix: &[Vec<f64>]) -> (usize, usize, usize, usize) {
+        let rows = matrix.len();
+        let cols = matrix[0].len();
+        let mut max_prod = 0.0;
+        let mut max = (0, 0, 0, 0);
+
+        for r0 in 0..rows {
+            for r1 in r0..rows {
+                for c0 in 0..cols {
+                    for c1 in c0..cols {
+                        let p: f64 = matrix[r0..=r1].iter().flat_map(|r| &r[c0..=c1]).product();
+
+                        if p > max_prod {
+                            max_prod = p;
+                            max = (r0, c0, r1, c1);
+                        }
+                    }
+                }
+            }
+        }
+
+        max
+    }
+
+    #[test]
+    fn test_submatrix_basic() {
+        let m = vec![
+            vec![0.5, 0.5, 0.5, 0.5],
+            vec![0.5, 2.0, 3.0, 0.5],
+            vec![0.5, 4.0, 5.0, 0.1],
+        ];
+        assert_eq!(max_prod_submatrix_real(&m), Ok((1, 1, 2, 2)));
+        assert_eq!(max_prod_submatrix_real(&m).unwrap(), max_prod_submatrix_brute_force(&m));
+
+        let m = vec![vec![0.5, 0.2], vec![0.1, 0.7]];
+        assert_eq!(max_prod_submatrix_real(&m), Ok((1, 1, 1, 1)));
+    }
+
+    #[test]
+    fn test_submatrix_non_rectangular() {
+        let m = vec![vec![1.0, 2.0], vec![3.0], vec![4.0, 5.0]];
+        assert_eq!(max_prod_submatrix_real(&m), Err(NonRectangularError { row: 1, expected: 2, found: 1 }));
+    }
+
+    #[test]
+    fn test_random_submatrix() {
+        let mut rng = test_rng();
+        for _ in 0..300 {
+            let rows = rng.gen_range(1..6);
+            let cols = rng.gen_range(1..6);
+            let m: Vec<Vec<f64>> = (0..rows)
+                .map(|_| Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(cols).collect())
+                .collect();
+
+            assert_eq!(max_prod_submatrix_real(&m).unwrap(), max_prod_submatrix_brute_force(&m), "m = {:?}", m);
+        }
+    }
+}
@@ -0,0 +1,1353 @@
+use num::{Integer, Signed};
+use num::traits::{CheckedMul, Num, SaturatingMul};
+
+use crate::error::{MaxProdError, OverflowError};
+use crate::int::MaxProdInt;
+use crate::non_empty::NonEmptySlice;
+use crate::result::MaxProdResult;
+use crate::span::Span;
+
+/// Tries every range, O(n³).
+///
+/// This is the reference the other algorithms are tested against, including which range
+/// wins on ties: of all ranges with the maximum product the one with the smallest start
+/// wins, then the one with the smallest end. If no range has a positive product the result
+/// is `(0, 0)`.
+///
+/// ```
+/// use max_prod::max_prod_brute_force;
+///
+/// // [0 .. 1], [3 .. 3], [3 .. 4] and [3 .. 5] all have a product of six
+/// assert_eq!(max_prod_brute_force([2u32, 3, 0, 6, 1, 1]), (0, 1));
+/// ```
+#[must_use]
+pub fn max_prod_brute_force<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    NonEmptySlice::try_from(arr.as_ref()).map_or((0, 0), max_prod_brute_force_non_empty)
+}
+
+/// [`max_prod_brute_force`] on a slice that is known not to be empty.
+///
+/// `max_prod_brute_force` checks for an empty slice and then calls this.
+///
+/// ```
+/// use max_prod::{max_prod_brute_force_non_empty, NonEmptySlice};
+///
+/// let arr = NonEmptySlice::try_from(&[2u32, 0, 3, 4][..]).unwrap();
+/// assert_eq!(max_prod_brute_force_non_empty(arr), (2, 3));
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_non_empty<T: Num + Copy + PartialOrd>(arr: NonEmptySlice<'_, T>) -> (usize, usize) {
+    let (_, i, j) = max_prod_brute_force_inner(&arr, |_, _| {}).unwrap();
+    (i, j)
+}
+
+/// Like [`max_prod_brute_force_with_value`] but returns a [`MaxProdResult`].
+///
+/// ```
+/// use max_prod::{max_prod_brute_force_result, MaxProdResult};
+///
+/// let res = max_prod_brute_force_result([2u32, 0, 3, 4]);
+/// assert_eq!(res, MaxProdResult { start: 2, end: 3, product: 12 });
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_result<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> MaxProdResult<T> {
+    max_prod_brute_force_with_value(arr).into()
+}
+
+/// Like [`max_prod_brute_force`] but returns a [`Span`] to slice the array with.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_span;
+///
+/// let arr = [2u32, 0, 3, 4];
+/// assert_eq!(max_prod_brute_force_span(arr).slice(&arr), &[3, 4]);
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_span<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> Span {
+    max_prod_brute_force(arr).into()
+}
+
+/// Like [`max_prod_brute_force`] but also returns the product of the winning range.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_with_value;
+///
+/// assert_eq!(max_prod_brute_force_with_value([2u32, 0, 3, 4]), (12, 2, 3));
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_with_value<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> (T, usize, usize) {
+    max_prod_brute_force_inner(arr.as_ref(), |_, _| {}).unwrap()
+}
+
+/// Number of starts [`max_prod_brute_force_with_progress`] tries between two calls of its
+/// callback.
+const PROGRESS_INTERVAL: usize = 64;
+
+/// Like [`max_prod_brute_force`] but calls `on_progress(i, n)` before trying the ranges
+/// starting at `i`, for every `i` that is a multiple of 64, and once more with `(n, n)` at
+/// the end, e.g. to show a progress bar for a large `arr`.
+///
+/// Trying the ranges of one start takes O(n²), so the calls are cheap compared to the work
+/// between them.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_with_progress;
+///
+/// let mut calls = Vec::new();
+/// let range = max_prod_brute_force_with_progress(&[2u32, 0, 3, 4], |i, n| calls.push((i, n)));
+/// assert_eq!(range, (2, 3));
+/// assert_eq!(calls, vec![(0, 4), (4, 4)]);
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_with_progress<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>, on_progress: impl FnMut(usize, usize)) -> (usize, usize) {
+    let (_, i, j) = max_prod_brute_force_inner(arr.as_ref(), on_progress).unwrap();
+    (i, j)
+}
+
+fn max_prod_brute_force_inner<T: Num + Copy + PartialOrd>(arr: &[T], mut on_progress: impl FnMut(usize, usize)) -> Result<(T, usize, usize), MaxProdError> {
+    let mut best = None;
+    let n = arr.len();
+
+    // every range in lexicographic order, so on ties the smallest start and end win
+    for i in 0..n {
+        if i % PROGRESS_INTERVAL == 0 {
+            on_progress(i, n);
+        }
+
+        for j in i..n {
+            // multiplied from the left starting at one, just like `prod`
+            let prod = arr[i..=j].iter().fold(T::one(), |p, &x| p * x);
+            consider(&mut best, prod, i, j);
+        }
+    }
+    on_progress(n, n);
+
+    best_or_default(best)
+}
+
+/// Like [`max_prod_brute_force`] but fails instead of wrapping on overflow.
+///
+/// The error holds the first range, in the order the ranges are tried, whose product
+/// overflows. Every range is multiplied from the left, so that is also the range at which
+/// `checked_mul` fails: its product is the first one that doesn't fit, all ranges tried
+/// before it did.
+///
+/// ```
+/// use max_prod::{max_prod_brute_force_checked, OverflowError};
+///
+/// assert_eq!(max_prod_brute_force_checked([2u8, 3, 0, 7]), Ok((3, 3)));
+/// // 3 * 10 * 10 = 300 doesn't fit into a u8
+/// assert_eq!(max_prod_brute_force_checked([3u8, 10, 10, 0]), Err(OverflowError { start: 0, end: 2 }));
+/// ```
+pub fn max_prod_brute_force_checked<T: Num + Copy + PartialOrd + CheckedMul>(arr: impl AsRef<[T]>) -> Result<(usize, usize), OverflowError> {
+    let arr = arr.as_ref();
+    let mut best = None;
+    let n = arr.len();
+
+    for i in 0..n {
+        for j in i..n {
+            let prod = arr[i..=j].iter()
+                .try_fold(T::one(), |p, x| p.checked_mul(x))
+                .ok_or(OverflowError { start: i, end: j })?;
+            consider(&mut best, prod, i, j);
+        }
+    }
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    Ok((i, j))
+}
+
+/// Keeps `(prod, i, j)` in `best` if `prod` is positive and larger than the best product so
+/// far, returns whether it did. `best` is `None` until some range has a positive product, so
+/// no range yet isn't confused with a range whose product is zero.
+pub(crate) fn consider<T: Num + Copy + PartialOrd>(best: &mut Option<(T, usize, usize)>, prod: T, i: usize, j: usize) -> bool {
+    let better = prod > T::zero() && best.is_none_or(|(p, _, _)| prod > p);
+
+    if better {
+        *best = Some((prod, i, j));
+    }
+
+    better
+}
+
+/// If no range has a positive product the result is `(0, 0)` with a product of zero.
+pub(crate) fn best_or_default<T: Num>(best: Option<(T, usize, usize)>) -> Result<(T, usize, usize), MaxProdError> {
+    let (max_prod, i, j) = best.unwrap_or((T::zero(), 0, 0));
+    check_range((i, j))?;
+    Ok((max_prod, i, j))
+}
+
+/// Like [`max_prod_brute_force`] but without bounds checks in the innermost loop.
+///
+/// The safe version already multiplies over subslices, which has no bounds checks to begin
+/// with, so this is barely faster, see `int/brute_force_unchecked` in the benchmarks.
+/// Indexing element by element with `get_unchecked` was even slower than that.
+///
+/// # Safety
+///
+/// `arr` must not be empty. [`try_max_prod_brute_force_unchecked`] checks this and is safe
+/// to call.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_unchecked;
+///
+/// // SAFETY: the array isn't empty
+/// let range = unsafe { max_prod_brute_force_unchecked(&[2u32, 0, 3, 4]) };
+/// assert_eq!(range, (2, 3));
+/// ```
+#[must_use]
+pub unsafe fn max_prod_brute_force_unchecked<T: Num + Copy + PartialOrd>(arr: &[T]) -> (usize, usize) {
+    debug_assert!(!arr.is_empty());
+
+    let mut best = None;
+    let n = arr.len();
+
+    for i in 0..n {
+        for j in i..n {
+            // SAFETY: i <= j < n
+            let range = unsafe { arr.get_unchecked(i..=j) };
+            let prod = range.iter().fold(T::one(), |p, &x| p * x);
+
+            consider(&mut best, prod, i, j);
+        }
+    }
+
+    let (_, i, j) = best.unwrap_or((T::zero(), 0, 0));
+    (i, j)
+}
+
+/// Safe version of [`max_prod_brute_force_unchecked`], fails with [`MaxProdError::Empty`]
+/// for an empty slice.
+///
+/// ```
+/// use max_prod::{try_max_prod_brute_force_unchecked, MaxProdError};
+///
+/// assert_eq!(try_max_prod_brute_force_unchecked([2u32, 0, 3, 4]), Ok((2, 3)));
+/// assert_eq!(try_max_prod_brute_force_unchecked::<u32>([]), Err(MaxProdError::Empty));
+/// ```
+pub fn try_max_prod_brute_force_unchecked<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> Result<(usize, usize), MaxProdError> {
+    let arr = NonEmptySlice::try_from(arr.as_ref())?;
+
+    // SAFETY: arr isn't empty
+    Ok(unsafe { max_prod_brute_force_unchecked(&arr) })
+}
+
+/// Like [`max_prod_brute_force`] but reuses the product of `arr[i..j]` for `arr[i..=j]`,
+/// O(n²). Returns exactly the same range, ties included.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_improved;
+///
+/// assert_eq!(max_prod_brute_force_improved([0.5, 4.0, 0.5, 3.0]), (1, 3));
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_improved<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    NonEmptySlice::try_from(arr.as_ref()).map_or((0, 0), max_prod_brute_force_improved_non_empty)
+}
+
+/// [`max_prod_brute_force_improved`] on a slice that is known not to be empty.
+///
+/// `max_prod_brute_force_improved` checks for an empty slice and then calls this.
+///
+/// ```
+/// use max_prod::{max_prod_brute_force_improved_non_empty, NonEmptySlice};
+///
+/// let arr = NonEmptySlice::try_from(&[0.5, 4.0, 0.5, 3.0][..]).unwrap();
+/// assert_eq!(max_prod_brute_force_improved_non_empty(arr), (1, 3));
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_improved_non_empty<T: Num + Copy + PartialOrd>(arr: NonEmptySlice<'_, T>) -> (usize, usize) {
+    let (_, i, j) = max_prod_brute_force_improved_inner(&arr).unwrap();
+    (i, j)
+}
+
+/// Like [`max_prod_brute_force_improved_with_value`] but returns a [`MaxProdResult`].
+///
+/// ```
+/// use max_prod::{max_prod_brute_force_improved_result, MaxProdResult};
+///
+/// let res = max_prod_brute_force_improved_result([2u32, 0, 3, 4]);
+/// assert_eq!(res, MaxProdResult { start: 2, end: 3, product: 12 });
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_improved_result<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> MaxProdResult<T> {
+    max_prod_brute_force_improved_with_value(arr).into()
+}
+
+/// Like [`max_prod_brute_force_improved`] but returns a [`Span`] to slice the array with.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_improved_span;
+///
+/// let arr = [2u32, 0, 3, 4];
+/// assert_eq!(max_prod_brute_force_improved_span(arr).slice(&arr), &[3, 4]);
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_improved_span<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> Span {
+    max_prod_brute_force_improved(arr).into()
+}
+
+/// Like [`max_prod_brute_force_improved`] but also returns the product of the winning range.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_improved_with_value;
+///
+/// assert_eq!(max_prod_brute_force_improved_with_value([0.5, 4.0, 0.5, 3.0]), (6.0, 1, 3));
+/// ```
+#[must_use]
+pub fn max_prod_brute_force_improved_with_value<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> (T, usize, usize) {
+    max_prod_brute_force_improved_inner(arr.as_ref()).unwrap()
+}
+
+fn max_prod_brute_force_improved_inner<T: Num + Copy + PartialOrd>(arr: &[T]) -> Result<(T, usize, usize), MaxProdError> {
+    let mut best = None;
+    let n = arr.len();
+
+    for i in 0..n {
+        let mut prod = T::one();
+        for (j, &x) in arr.iter().enumerate().skip(i) {
+            prod = prod * x;
+            consider(&mut best, prod, i, j);
+        }
+    }
+
+    best_or_default(best)
+}
+
+/// Like [`max_prod_brute_force_improved`] but fails instead of wrapping on overflow.
+///
+/// ```
+/// use max_prod::{max_prod_brute_force_improved_checked, OverflowError};
+///
+/// assert_eq!(max_prod_brute_force_improved_checked([2u8, 3, 0, 7]), Ok((3, 3)));
+/// assert_eq!(max_prod_brute_force_improved_checked([16u8, 16]), Err(OverflowError { start: 0, end: 1 }));
+/// ```
+pub fn max_prod_brute_force_improved_checked<T: Num + Copy + PartialOrd + CheckedMul>(arr: impl AsRef<[T]>) -> Result<(usize, usize), OverflowError> {
+    let arr = arr.as_ref();
+    let mut best = None;
+    let n = arr.len();
+
+    for i in 0..n {
+        let mut prod = T::one();
+        for (j, &x) in arr.iter().enumerate().skip(i) {
+            prod = prod.checked_mul(&x).ok_or(OverflowError { start: i, end: j })?;
+            consider(&mut best, prod, i, j);
+        }
+    }
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    Ok((i, j))
+}
+
+/// Fast O(n) algorithm for unsigned integers, zeros split the array into runs.
+///
+/// Like every algorithm here it accepts anything that can be viewed as a slice:
+///
+/// ```
+/// use max_prod::max_prod_fast_int;
+///
+/// let v: Vec<u32> = vec![0, 2, 3, 4];
+/// assert_eq!(max_prod_fast_int(&v), (1, 3));
+/// assert_eq!(max_prod_fast_int(v), (1, 3));
+///
+/// assert_eq!(max_prod_fast_int([4u32, 2, 0, 3]), (0, 1));
+///
+/// let a = [5u8, 0, 2, 1];
+/// assert_eq!(max_prod_fast_int(&a[1..]), (1, 1));
+/// ```
+///
+/// Ties are broken like in [`max_prod_brute_force`], the smallest start and then the
+/// smallest end win, so both return exactly the same range as long as no product
+/// overflows. Within a run leading ones are part of the range and trailing ones aren't.
+///
+/// If `arr` only contains zeros `(0, 0)` is returned with a product of zero, just like the
+/// brute force, even though no range has a positive product. Use
+/// [`try_max_prod_fast_int_positive`] to get `None` in that case.
+#[must_use]
+pub fn max_prod_fast_int<T: MaxProdInt>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    NonEmptySlice::try_from(arr.as_ref()).map_or((0, 0), max_prod_fast_int_non_empty)
+}
+
+/// [`max_prod_fast_int`] on a slice that is known not to be empty.
+///
+/// `max_prod_fast_int` checks for an empty slice and then calls this.
+///
+/// ```
+/// use max_prod::{max_prod_fast_int_non_empty, NonEmptySlice};
+///
+/// let arr = NonEmptySlice::try_from(&[2u32, 0, 3, 4][..]).unwrap();
+/// assert_eq!(max_prod_fast_int_non_empty(arr), (2, 3));
+///
+/// // still (0, 0) if there are only zeros
+/// let zeros = NonEmptySlice::try_from(&[0u32, 0][..]).unwrap();
+/// assert_eq!(max_prod_fast_int_non_empty(zeros), (0, 0));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_non_empty<T: MaxProdInt>(arr: NonEmptySlice<'_, T>) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_int_inner(&arr, None).unwrap();
+    (i, j)
+}
+
+/// Like [`max_prod_fast_int_with_value`] but returns a [`MaxProdResult`].
+///
+/// ```
+/// use max_prod::{max_prod_fast_int_result, MaxProdResult};
+///
+/// let res = max_prod_fast_int_result([2u32, 0, 3, 4]);
+/// assert_eq!(res, MaxProdResult { start: 2, end: 3, product: 12 });
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_result<T: MaxProdInt>(arr: impl AsRef<[T]>) -> MaxProdResult<T> {
+    max_prod_fast_int_with_value(arr).into()
+}
+
+/// Like [`max_prod_fast_int`] but returns a [`Span`] to slice the array with.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_span;
+///
+/// let arr = [2u32, 0, 3, 4];
+/// assert_eq!(max_prod_fast_int_span(arr).slice(&arr), &[3, 4]);
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_span<T: MaxProdInt>(arr: impl AsRef<[T]>) -> Span {
+    max_prod_fast_int(arr).into()
+}
+
+/// Like [`max_prod_fast_int`] but also returns the product of the winning range.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_with_value;
+///
+/// assert_eq!(max_prod_fast_int_with_value([2u32, 0, 3, 4]), (12, 2, 3));
+/// assert_eq!(max_prod_fast_int_with_value([0u32, 0]), (0, 0, 0));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_with_value<T: MaxProdInt>(arr: impl AsRef<[T]>) -> (T, usize, usize) {
+    max_prod_fast_int_inner(arr.as_ref(), None).unwrap()
+}
+
+/// Like [`max_prod_fast_int`] but stops as soon as a range reaches a product of at least
+/// `upper_bound` and returns it, e.g. if the maximum product is known in advance or any
+/// range that large is good enough.
+///
+/// If no range reaches the bound the whole array is scanned and the result is exactly the
+/// one of `max_prod_fast_int`. A single element of at least `upper_bound` returns at once.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_bounded;
+///
+/// assert_eq!(max_prod_fast_int_bounded([2u32, 0, 9, 3, 0, 100], 9), (2, 2));
+/// assert_eq!(max_prod_fast_int_bounded([2u32, 0, 9, 3, 0, 100], 1000), (5, 5));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_bounded<T: MaxProdInt>(arr: impl AsRef<[T]>, upper_bound: T) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_int_inner(arr.as_ref(), Some(upper_bound)).unwrap();
+    (i, j)
+}
+
+fn max_prod_fast_int_inner<T: MaxProdInt>(arr: &[T], upper_bound: Option<T>) -> Result<(T, usize, usize), MaxProdError> {
+    let mut best = None;
+
+    let mut start = 0;
+    let mut current_prod = T::zero();
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x == T::zero() {
+            current_prod = T::zero();
+            continue;
+        }
+
+        if current_prod == T::zero() {
+            current_prod = T::one();
+            start = i;
+        }
+        current_prod = current_prod * x;
+
+        if consider(&mut best, current_prod, start, i) && upper_bound.is_some_and(|b| current_prod >= b) {
+            break;
+        }
+    }
+
+    best_or_default(best)
+}
+
+/// Like [`max_prod_fast_int`] but returns the winning subarray itself, empty if `arr` is empty.
+///
+/// ```
+/// use max_prod::max_prod_subslice_int;
+///
+/// let v = vec![2u32, 0, 3, 4, 0, 5];
+/// assert_eq!(max_prod_subslice_int(&v), &[3, 4]);
+/// ```
+#[must_use]
+pub fn max_prod_subslice_int<T: MaxProdInt>(arr: &[T]) -> &[T] {
+    if arr.is_empty() {
+        return arr;
+    }
+
+    let (i, j) = max_prod_fast_int(arr);
+    &arr[i..=j]
+}
+
+/// Like [`max_prod_fast_int`] but fails instead of wrapping on overflow.
+///
+/// The error contains the range whose running product overflowed.
+///
+/// ```
+/// use max_prod::{max_prod_fast_int_checked, OverflowError};
+///
+/// assert_eq!(max_prod_fast_int_checked([2u8, 3, 0, 7]), Ok((3, 3)));
+/// assert_eq!(max_prod_fast_int_checked([2u8, 0, 16, 16]), Err(OverflowError { start: 2, end: 3 }));
+/// ```
+pub fn max_prod_fast_int_checked<T: MaxProdInt + CheckedMul>(arr: impl AsRef<[T]>) -> Result<(usize, usize), OverflowError> {
+    let arr = arr.as_ref();
+    let mut best = None;
+
+    let mut start = 0;
+    let mut current_prod = T::zero();
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x == T::zero() {
+            current_prod = T::zero();
+            continue;
+        }
+
+        if current_prod == T::zero() {
+            current_prod = T::one();
+            start = i;
+        }
+        current_prod = current_prod.checked_mul(&x).ok_or(OverflowError { start, end: i })?;
+
+        consider(&mut best, current_prod, start, i);
+    }
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    Ok((i, j))
+}
+
+/// Like [`max_prod_fast_int`] but saturates at `T::max_value()` instead of wrapping on
+/// overflow, and in debug builds panics like unchecked arithmetic would.
+///
+/// The ranges are compared by their saturated products, so once several products reach
+/// the maximum they tie and the first one wins, even if its true product is smaller.
+/// Near overflow the result can differ from the one with exact arithmetic, use
+/// [`max_prod_fast_int_checked`] to find out whether it does.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_saturating;
+///
+/// assert_eq!(max_prod_fast_int_saturating([2u8, 0, 3, 4]), (2, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_saturating<T: MaxProdInt + CheckedMul + SaturatingMul>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    max_prod_fast_int_saturating_inner(arr.as_ref(), cfg!(debug_assertions))
+}
+
+fn max_prod_fast_int_saturating_inner<T: MaxProdInt + CheckedMul + SaturatingMul>(arr: &[T], panic_on_overflow: bool) -> (usize, usize) {
+    let mut best = None;
+    let mut start = 0;
+    let mut current_prod = T::zero();
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x == T::zero() {
+            current_prod = T::zero();
+            continue;
+        }
+
+        if current_prod == T::zero() {
+            current_prod = T::one();
+            start = i;
+        }
+
+        if panic_on_overflow && current_prod.checked_mul(&x).is_none() {
+            panic!("product of [{} .. {}] overflows", start, i);
+        }
+        current_prod = current_prod.saturating_mul(&x);
+
+        consider(&mut best, current_prod, start, i);
+    }
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    (i, j)
+}
+
+/// Fast algorithm for signed integers, negative values are allowed.
+///
+/// Like the brute force, if no range has a positive product `(0, 0)` is returned.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_signed;
+///
+/// assert_eq!(max_prod_fast_int_signed([-2i32, 0, -3, -4, 5]), (2, 4));
+/// // no range has a positive product
+/// assert_eq!(max_prod_fast_int_signed([-2i32, 0, -3]), (0, 0));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_signed<T: Num + Integer + Copy + Signed>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_int_signed_with_value(arr);
+    (i, j)
+}
+
+/// Like [`max_prod_fast_int_signed_with_value`] but returns a [`MaxProdResult`].
+///
+/// ```
+/// use max_prod::{max_prod_fast_int_signed_result, MaxProdResult};
+///
+/// let res = max_prod_fast_int_signed_result([-2i32, 0, -3, -4, 5]);
+/// assert_eq!(res, MaxProdResult { start: 2, end: 4, product: 60 });
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_signed_result<T: Num + Integer + Copy + Signed>(arr: impl AsRef<[T]>) -> MaxProdResult<T> {
+    max_prod_fast_int_signed_with_value(arr).into()
+}
+
+/// Like [`max_prod_fast_int_signed`] but also returns the product of the winning range.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_signed_with_value;
+///
+/// assert_eq!(max_prod_fast_int_signed_with_value([-2i32, 0, -3, -4, 5]), (60, 2, 4));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_signed_with_value<T: Num + Integer + Copy + Signed>(arr: impl AsRef<[T]>) -> (T, usize, usize) {
+    max_prod_kadane_with_value(arr.as_ref()).unwrap()
+}
+
+/// Like [`max_prod_fast_int`] for signed integers, but maximizes the product of the absolute
+/// values, so signs are ignored and only zeros split the array into runs.
+///
+/// `T::min_value()` has no absolute value in `T` and overflows.
+///
+/// ```
+/// use max_prod::max_abs_prod_fast_int;
+///
+/// assert_eq!(max_abs_prod_fast_int([3i64, 0, -2, 5, 0, 4]), (2, 3));
+/// ```
+#[must_use]
+pub fn max_abs_prod_fast_int<T: Num + Integer + Copy + Signed>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let (_, i, j) = max_abs_prod_fast_int_with_value(arr);
+    (i, j)
+}
+
+/// Like [`max_abs_prod_fast_int`] but also returns the product of the absolute values of
+/// the winning range.
+///
+/// ```
+/// use max_prod::max_abs_prod_fast_int_with_value;
+///
+/// assert_eq!(max_abs_prod_fast_int_with_value([-2i32, 0, -3, 5]), (15, 2, 3));
+/// ```
+#[must_use]
+pub fn max_abs_prod_fast_int_with_value<T: Num + Integer + Copy + Signed>(arr: impl AsRef<[T]>) -> (T, usize, usize) {
+    let arr = arr.as_ref();
+    let mut best = None;
+
+    let mut start = 0;
+    let mut current_prod = T::zero();
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x == T::zero() {
+            current_prod = T::zero();
+            continue;
+        }
+
+        if current_prod == T::zero() {
+            current_prod = T::one();
+            start = i;
+        }
+        current_prod = current_prod * x.abs();
+
+        consider(&mut best, current_prod, start, i);
+    }
+
+    best_or_default(best).unwrap()
+}
+
+pub(crate) fn max_prod_kadane_with_value<T: Num + Copy + PartialOrd>(arr: &[T]) -> Result<(T, usize, usize), MaxProdError> {
+    let mut best: Option<(T, usize, usize)> = None;
+
+    // largest and smallest product of a range ending at the current index,
+    // together with the start of that range; None right after a zero
+    let mut current: Option<((T, usize), (T, usize))> = None;
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x == T::zero() {
+            current = None;
+            continue;
+        }
+
+        let (hi, lo) = match current {
+            None => ((x, i), (x, i)),
+            Some((hi, lo)) => {
+                let mut new_hi = (x, i);
+                let mut new_lo = (x, i);
+
+                // two negatives flip back to a large positive, so the new
+                // maximum can come from the old minimum and vice versa
+                for (p, start) in [(hi.0 * x, hi.1), (lo.0 * x, lo.1)] {
+                    if p > new_hi.0 || (p == new_hi.0 && start < new_hi.1) {
+                        new_hi = (p, start);
+                    }
+                    if p < new_lo.0 || (p == new_lo.0 && start < new_lo.1) {
+                        new_lo = (p, start);
+                    }
+                }
+
+                (new_hi, new_lo)
+            }
+        };
+        current = Some((hi, lo));
+
+        // not consider, a later range can start before the best one and then wins a tie
+        if hi.0 > T::zero() && best.is_none_or(|(p, start, _)| hi.0 > p || (hi.0 == p && hi.1 < start)) {
+            best = Some((hi.0, hi.1, i));
+        }
+    }
+
+    best_or_default(best)
+}
+
+/// Fails with [`MaxProdError::Empty`] for an empty slice instead of returning `(0, 0)`.
+///
+/// ```
+/// use max_prod::{try_max_prod_brute_force, MaxProdError};
+///
+/// assert_eq!(try_max_prod_brute_force([2u32, 0, 3, 4]), Ok((2, 3)));
+/// assert_eq!(try_max_prod_brute_force::<u32>([]), Err(MaxProdError::Empty));
+/// ```
+pub fn try_max_prod_brute_force<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> Result<(usize, usize), MaxProdError> {
+    let (_, i, j) = max_prod_brute_force_inner(&NonEmptySlice::try_from(arr.as_ref())?, |_, _| {})?;
+    Ok((i, j))
+}
+
+/// Fails with [`MaxProdError::Empty`] for an empty slice instead of returning `(0, 0)`.
+///
+/// ```
+/// use max_prod::{try_max_prod_brute_force_improved, MaxProdError};
+///
+/// assert_eq!(try_max_prod_brute_force_improved([2u32, 0, 3, 4]), Ok((2, 3)));
+/// assert_eq!(try_max_prod_brute_force_improved::<u32>([]), Err(MaxProdError::Empty));
+/// ```
+pub fn try_max_prod_brute_force_improved<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> Result<(usize, usize), MaxProdError> {
+    let (_, i, j) = max_prod_brute_force_improved_inner(&NonEmptySlice::try_from(arr.as_ref())?)?;
+    Ok((i, j))
+}
+
+/// Fails with [`MaxProdError::Empty`] for an empty slice instead of returning `(0, 0)`.
+///
+/// ```
+/// use max_prod::{try_max_prod_fast_int, MaxProdError};
+///
+/// assert_eq!(try_max_prod_fast_int([2u32, 0, 3, 4]), Ok((2, 3)));
+/// assert_eq!(try_max_prod_fast_int::<u32>([]), Err(MaxProdError::Empty));
+/// ```
+pub fn try_max_prod_fast_int<T: MaxProdInt>(arr: impl AsRef<[T]>) -> Result<(usize, usize), MaxProdError> {
+    let (_, i, j) = max_prod_fast_int_inner(&NonEmptySlice::try_from(arr.as_ref())?, None)?;
+    Ok((i, j))
+}
+
+/// Like [`max_prod_fast_int`] but returns `None` if the maximum product is zero, i.e. if
+/// `arr` is empty or only contains zeros.
+///
+/// ```
+/// use max_prod::try_max_prod_fast_int_positive;
+///
+/// assert_eq!(try_max_prod_fast_int_positive([0u32, 3, 0]), Some((1, 1)));
+/// assert_eq!(try_max_prod_fast_int_positive([0u32, 0, 0]), None);
+/// ```
+#[must_use]
+pub fn try_max_prod_fast_int_positive<T: MaxProdInt>(arr: impl AsRef<[T]>) -> Option<(usize, usize)> {
+    let (p, i, j) = max_prod_fast_int_with_value(arr);
+    (p != T::zero()).then_some((i, j))
+}
+
+/// Fails with [`MaxProdError::Empty`] for an empty slice instead of returning `(0, 0)`.
+///
+/// ```
+/// use max_prod::{try_max_prod_fast_int_signed, MaxProdError};
+///
+/// assert_eq!(try_max_prod_fast_int_signed([-2i32, -3, 0, 4]), Ok((0, 1)));
+/// assert_eq!(try_max_prod_fast_int_signed::<i32>([]), Err(MaxProdError::Empty));
+/// ```
+pub fn try_max_prod_fast_int_signed<T: Num + Integer + Copy + Signed>(arr: impl AsRef<[T]>) -> Result<(usize, usize), MaxProdError> {
+    let (_, i, j) = max_prod_kadane_with_value(&NonEmptySlice::try_from(arr.as_ref())?)?;
+    Ok((i, j))
+}
+
+pub(crate) fn check_range((start, end): (usize, usize)) -> Result<(), MaxProdError> {
+    if start <= end {
+        Ok(())
+    } else {
+        Err(MaxProdError::InvalidRange { start, end })
+    }
+}
+
+/// The product of `arr[i..=j]`, panics if the range is out of bounds.
+///
+/// ```
+/// use max_prod::prod;
+///
+/// assert_eq!(prod([2u32, 3, 4], 1, 2), 12);
+/// assert_eq!(prod([2u32, 3, 4], 0, 0), 2);
+/// ```
+#[must_use]
+pub fn prod<T: Num + Copy>(arr: impl AsRef<[T]>, i: usize, j: usize) -> T {
+    let arr = arr.as_ref();
+    let mut prod = T::one();
+    for &x in &arr[i..=j] {
+        prod = prod * x;
+    }
+
+    prod
+}
+
+/// Like [`prod`] but returns `None` if the product overflows instead of wrapping.
+///
+/// ```
+/// use max_prod::checked_prod;
+///
+/// assert_eq!(checked_prod([3u32, 70000, 70000], 0, 1), Some(210000));
+/// assert_eq!(checked_prod([3u32, 70000, 70000], 0, 2), None);
+/// ```
+#[must_use]
+pub fn checked_prod<T: Num + Copy + CheckedMul>(arr: impl AsRef<[T]>, i: usize, j: usize) -> Option<T> {
+    arr.as_ref()[i..=j].iter().try_fold(T::one(), |p, x| p.checked_mul(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    /// The index based brute force from before the iterator rewrite.
+    #[allow(clippy::needless_range_loop)]
+    fn max_prod_brute_force_loops<T: Num + Copy + PartialOrd>(arr: &[T]) -> (T, usize, usize) {
+        let mut max_prod = T::zero();
+        let mut max = (0, 0);
+        let n = arr.len();
+
+        for i in 0..n {
+            for j in i..n {
+                let mut prod = T::one();
+
+                for k in i..=j {
+                    prod = prod * arr[k];
+                }
+
+                if prod > max_prod {
+                    max_prod = prod;
+                    max = (i, j);
+                }
+            }
+        }
+
+        (max_prod, max.0, max.1)
+    }
+
+    /// Integers ordered backwards, so every range with a negative product is positive and
+    /// zero is the largest, not the smallest, of all products of non-negative numbers.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Rev(i64);
+
+    impl PartialOrd for Rev {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            other.0.partial_cmp(&self.0)
+        }
+    }
+
+    impl num::Zero for Rev {
+        fn zero() -> Self {
+            Rev(0)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl num::One for Rev {
+        fn one() -> Self {
+            Rev(1)
+        }
+    }
+
+    macro_rules! rev_op {
+        ($t:ident, $f:ident, $op:tt) => {
+            impl core::ops::$t for Rev {
+                type Output = Rev;
+
+                fn $f(self, rhs: Rev) -> Rev {
+                    Rev(self.0 $op rhs.0)
+                }
+            }
+        };
+    }
+
+    rev_op!(Add, add, +);
+    rev_op!(Sub, sub, -);
+    rev_op!(Mul, mul, *);
+    rev_op!(Div, div, /);
+    rev_op!(Rem, rem, %);
+
+    impl Num for Rev {
+        type FromStrRadixErr = <i64 as Num>::FromStrRadixErr;
+
+        fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+            i64::from_str_radix(s, radix).map(Rev)
+        }
+    }
+
+    #[test]
+    fn test_brute_force_rev() {
+        // the "largest" product is the most negative one
+        let a = [Rev(2), Rev(-3), Rev(4), Rev(1)];
+        assert_eq!(max_prod_brute_force_with_value(a), (Rev(-24), 0, 2));
+        assert_eq!(max_prod_brute_force_improved_with_value(a), (Rev(-24), 0, 2));
+
+        // no product is "positive", not even the zero ones, so nothing wins
+        let a = [Rev(2), Rev(0), Rev(3)];
+        assert_eq!(max_prod_brute_force_with_value(a), (Rev(0), 0, 0));
+        assert_eq!(max_prod_brute_force_improved_with_value(a), (Rev(0), 0, 0));
+
+        let a = [Rev(5), Rev(-1)];
+        assert_eq!(max_prod_brute_force_with_value(a), (Rev(-5), 0, 1));
+    }
+
+    #[test]
+    fn test_random_brute_force_loops() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(0..25);
+
+            let a: Vec<f64> = Uniform::new(-2.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            let (p, i, j) = max_prod_brute_force_with_value(&a);
+            let (q, k, l) = max_prod_brute_force_loops(&a);
+            assert_eq!((p.to_bits(), i, j), (q.to_bits(), k, l), "{a:?}");
+
+            let a: Vec<i64> = Uniform::new_inclusive(-3, 3).sample_iter(&mut rng).take(n).collect();
+            assert_eq!(max_prod_brute_force_with_value(&a), max_prod_brute_force_loops(&a), "{a:?}");
+        }
+    }
+
+    #[test]
+    fn test_random_brute_forces_zeros() {
+        let mut rng = test_rng();
+        for _ in 0..2000 {
+            let n = rng.gen_range(1..20);
+            let mut a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            for _ in 0..rng.gen_range(1..=3) {
+                a[rng.gen_range(0..n)] = 0.0;
+            }
+
+            // both multiply every range from the left, so even the rounding is the same
+            let (p, i, j) = max_prod_brute_force_with_value(&a);
+            let (q, k, l) = max_prod_brute_force_improved_with_value(&a);
+            assert_eq!((p.to_bits(), i, j), (q.to_bits(), k, l), "a = {:?}", a);
+        }
+
+        // every product is zero
+        let a = [0.0, 0.0, 0.0];
+        assert_eq!(max_prod_brute_force_with_value(a), (0.0, 0, 0));
+        assert_eq!(max_prod_brute_force_improved_with_value(a), (0.0, 0, 0));
+    }
+
+    #[test]
+    fn test_random_brute_force_unchecked() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(1..25);
+
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            assert_eq!(try_max_prod_brute_force_unchecked(&a), Ok(max_prod_brute_force(&a)), "{a:?}");
+
+            let a: Vec<i64> = Uniform::new_inclusive(-3, 3).sample_iter(&mut rng).take(n).collect();
+            assert_eq!(try_max_prod_brute_force_unchecked(&a), Ok(max_prod_brute_force(&a)), "{a:?}");
+        }
+
+        assert_eq!(try_max_prod_brute_force_unchecked::<u32>([]), Err(MaxProdError::Empty));
+    }
+
+    #[test]
+    fn test_random_int() {
+        let mut rng = test_rng();
+        for _ in 0..3000 {
+            let n = rng.gen_range(1..=40);
+            let mut a: Vec<u128> = Uniform::new_inclusive(0, 6).sample_iter(&mut rng).take(n).collect();
+            a[rng.gen_range(0..n)] = 0;
+
+            let (p, i, j) = max_prod_brute_force_with_value(&a);
+            assert_eq!(prod(&a, i, j), p, "{a:?}");
+
+            // same ranges, not just ranges with the same product
+            for (q, k, l) in [max_prod_brute_force_improved_with_value(&a), max_prod_fast_int_with_value(&a)] {
+                assert_eq!((k, l), (i, j), "{a:?}");
+                assert_eq!(q, p, "{a:?}");
+                assert_eq!(prod(&a, k, l), p, "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ties_exhaustive() {
+        // every array of length up to 6 over {0, 1, 2, 4}, many of them have ties
+        for n in 0..=6 {
+            for code in 0..4usize.pow(n) {
+                let a: Vec<u32> = (0..n).map(|k| [0, 1, 2, 4][code / 4usize.pow(k) % 4]).collect();
+
+                let expected = max_prod_brute_force(&a);
+                assert_eq!(max_prod_brute_force_improved(&a), expected, "{a:?}");
+                assert_eq!(max_prod_fast_int(&a), expected, "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_bounded() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(1..30);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 5).sample_iter(&mut rng).take(n).collect();
+            let (p, i, j) = max_prod_fast_int_with_value(&a);
+
+            // a bound that isn't reached doesn't change anything, the maximum itself is
+            // reached by the same range
+            assert_eq!(max_prod_fast_int_bounded(&a, p + 1), (i, j), "{a:?}");
+            assert_eq!(max_prod_fast_int_bounded(&a, u64::MAX), (i, j), "{a:?}");
+            if p > 0 {
+                assert_eq!(max_prod_fast_int_bounded(&a, p), (i, j), "{a:?}");
+            }
+
+            // a lower bound stops at the first range reaching it
+            let (k, l) = max_prod_fast_int_bounded(&a, 2);
+            assert!(p < 2 || prod(&a, k, l) >= 2, "{a:?}");
+        }
+    }
+
+    #[test]
+    fn test_random_int_signed() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let mut a: Vec<i32> = Uniform::new_inclusive(-4, 4).sample_iter(&mut rng).take(15).collect();
+
+            let n = a.len();
+            a[Uniform::new(0, n).sample(&mut rng)] = 0;
+
+            assert_eq!(max_prod_fast_int_signed(&a), max_prod_brute_force(&a));
+            assert_eq!(max_prod_fast_int_signed(&a), max_prod_brute_force_improved(&a));
+        }
+    }
+
+    #[test]
+    fn test_abs_basic() {
+        assert_eq!(max_abs_prod_fast_int_with_value([-3i64, 2, 0, 5]), (6, 0, 1));
+        assert_eq!(max_abs_prod_fast_int_with_value([-3i64, 0, -1, -7]), (7, 2, 3));
+        assert_eq!(max_abs_prod_fast_int_with_value([0i64, -1, 0]), (1, 1, 1));
+        assert_eq!(max_abs_prod_fast_int_with_value([0i64, 0]), (0, 0, 0));
+        assert_eq!(max_abs_prod_fast_int_with_value::<i64>([]), (0, 0, 0));
+
+        // the signed product of the same array has a different winner
+        assert_eq!(max_prod_fast_int_signed([-3i64, 2, 0, 5]), (3, 3));
+    }
+
+    #[test]
+    fn test_random_abs() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let a: Vec<i64> = Uniform::new_inclusive(-4, 4).sample_iter(&mut rng).take(15).collect();
+            let abs: Vec<i64> = a.iter().map(|x| x.abs()).collect();
+
+            assert_eq!(max_abs_prod_fast_int_with_value(&a), max_prod_brute_force_with_value(&abs), "{a:?}");
+        }
+    }
+
+    #[test]
+    fn test_int_signed_basic() {
+        let a = vec![-2i32, 3, -4];
+        assert_eq!(max_prod_fast_int_signed_with_value(&a), (24, 0, 2));
+
+        let a = vec![-1i32, 2, -1];
+        assert_eq!(max_prod_fast_int_signed(&a), (0, 2));
+        assert_eq!(max_prod_fast_int_signed(&a), max_prod_brute_force(&a));
+
+        let a = vec![-3i64, 0, -2, 0, -5];
+        assert_eq!(max_prod_fast_int_signed(&a), max_prod_brute_force(&a));
+    }
+
+    #[test]
+    fn test_int_checked_overflow() {
+        let a: Vec<u64> = vec![1, 0, 1 << 40, 1 << 30, 7];
+        let err = OverflowError { start: 2, end: 3 };
+        assert_eq!(max_prod_fast_int_checked(&a), Err(err));
+        assert_eq!(max_prod_brute_force_improved_checked(&a), Err(err));
+
+        let a: Vec<u64> = vec![u64::MAX, 2];
+        assert_eq!(max_prod_fast_int_checked(&a), Err(OverflowError { start: 0, end: 1 }));
+        assert_eq!(max_prod_brute_force_improved_checked(&a), Err(OverflowError { start: 0, end: 1 }));
+    }
+
+    #[test]
+    fn test_brute_force_progress() {
+        let a: Vec<u32> = (0..200).map(|k| k % 3).collect();
+        let mut calls = Vec::new();
+
+        assert_eq!(max_prod_brute_force_with_progress(&a, |i, n| calls.push((i, n))), max_prod_brute_force(&a));
+        assert_eq!(calls, vec![(0, 200), (64, 200), (128, 200), (192, 200), (200, 200)]);
+
+        let mut count = 0;
+        let _ = max_prod_brute_force_with_progress(&a[..128], |_, _| count += 1);
+        assert_eq!(count, 3);
+
+        let mut count = 0;
+        assert_eq!(max_prod_brute_force_with_progress::<u32>([], |_, _| count += 1), (0, 0));
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_brute_force_checked_overflow() {
+        // [1 .. 1] and [1 .. 2] fit, 5 * 7 * 8 = 280 overflows at [1 .. 3]
+        let a = [0u8, 5, 7, 8, 1];
+        assert_eq!(max_prod_brute_force_checked(a), Err(OverflowError { start: 1, end: 3 }));
+        assert_eq!(max_prod_brute_force_checked(a), max_prod_brute_force_improved_checked(a));
+
+        let a = [2u8, 100, 0, 200, 200];
+        assert_eq!(max_prod_brute_force_checked(a), Err(OverflowError { start: 3, end: 4 }));
+        assert_eq!(max_prod_brute_force_checked::<u8>([]), Ok((0, 0)));
+    }
+
+    #[test]
+    fn test_random_brute_force_checked() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(0..12);
+            let a: Vec<u8> = Uniform::new_inclusive(0, 6).sample_iter(&mut rng).take(n).collect();
+            let expected = max_prod_brute_force_improved_checked(&a);
+
+            assert_eq!(max_prod_brute_force_checked(&a), expected, "a = {:?}", a);
+            if let Ok(range) = expected {
+                assert_eq!(range, max_prod_brute_force(&a), "a = {:?}", a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_int_checked_no_overflow() {
+        let mut rng = test_rng();
+        let a: Vec<u64> = vec![1 << 20, 1 << 20, 0, 1 << 30, 1 << 30, 3, 0, u64::MAX];
+        assert_eq!(max_prod_fast_int_checked(&a), Ok(max_prod_fast_int(&a)));
+        assert_eq!(max_prod_brute_force_improved_checked(&a), Ok(max_prod_brute_force_improved(&a)));
+        assert_eq!(max_prod_fast_int_checked(&a), Ok((7, 7)));
+
+        for _ in 0..100 {
+            let a: Vec<u128> = Uniform::new_inclusive(0, 10).sample_iter(&mut rng).take(50).collect();
+            assert_eq!(max_prod_fast_int_checked(&a), max_prod_brute_force_improved_checked(&a));
+        }
+    }
+
+    #[test]
+    fn test_saturating() {
+        let a = [1u32 << 10, 1 << 10, 0, 5, 3];
+        assert_eq!(max_prod_fast_int_saturating(a), max_prod_fast_int(a));
+        assert_eq!(max_prod_fast_int_saturating(a), (0, 1));
+
+        // [2 .. 3] is the true maximum, but both products saturate and the first one wins
+        let a = [1u32 << 16, 1 << 16, 0, 1 << 20, 1 << 20];
+        assert_eq!(max_prod_fast_int_saturating_inner(&a, false), (0, 1));
+        assert_eq!(max_prod_fast_int_checked(a), Err(OverflowError { start: 0, end: 1 }));
+
+        let a = [3u8, 0, 16, 16, 2];
+        assert_eq!(max_prod_fast_int_saturating_inner(&a, false), (2, 3));
+        assert_eq!(max_prod_fast_int_saturating_inner(&[0u8, 0], false), (0, 0));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "product of [1 .. 2] overflows"))]
+    fn test_saturating_overflow() {
+        assert_eq!(max_prod_fast_int_saturating([0u8, 16, 16]), (1, 2));
+    }
+
+    #[test]
+    fn test_random_saturating() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 3).sample_iter(&mut rng).take(30).collect();
+            assert_eq!(max_prod_fast_int_saturating(&a), max_prod_fast_int(&a));
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let a: Vec<u32> = vec![];
+        assert_eq!(try_max_prod_brute_force(&a), Err(MaxProdError::Empty));
+        assert_eq!(try_max_prod_brute_force_improved(&a), Err(MaxProdError::Empty));
+        assert_eq!(try_max_prod_fast_int(&a), Err(MaxProdError::Empty));
+        assert_eq!(max_prod_fast_int(&a), (0, 0));
+        assert_eq!(max_prod_brute_force(&a), (0, 0));
+        assert_eq!(max_prod_brute_force_improved(&a), (0, 0));
+
+        let a: Vec<i32> = vec![];
+        assert_eq!(try_max_prod_fast_int_signed(&a), Err(MaxProdError::Empty));
+        assert_eq!(max_prod_fast_int_signed(&a), (0, 0));
+    }
+
+    #[test]
+    fn test_subslice_int() {
+        let mut rng = test_rng();
+        for _ in 0..100 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 10).sample_iter(&mut rng).take(15).collect();
+            let (p, _, _) = max_prod_fast_int_with_value(&a);
+            assert_eq!(max_prod_subslice_int(&a).iter().product::<u64>(), p);
+        }
+
+        let a: Vec<u32> = vec![];
+        assert!(max_prod_subslice_int(&a).is_empty());
+    }
+
+    #[test]
+    fn test_try_non_empty() {
+        let a = vec![0u32, 2, 3, 4];
+        assert_eq!(try_max_prod_fast_int(&a), Ok((1, 3)));
+        assert_eq!(try_max_prod_brute_force(&a), Ok((1, 3)));
+        assert_eq!(try_max_prod_brute_force_improved(&a), Ok((1, 3)));
+
+        let a = vec![-2i32, 0, -3, -4];
+        assert_eq!(try_max_prod_fast_int_signed(&a), Ok((2, 3)));
+    }
+
+    #[test]
+    fn test_try_positive() {
+        assert_eq!(try_max_prod_fast_int_positive([0u32, 0, 0]), None);
+        assert_eq!(try_max_prod_fast_int_positive([0u32]), None);
+        assert_eq!(try_max_prod_fast_int_positive::<u32>([]), None);
+        assert_eq!(try_max_prod_fast_int_positive([0u32, 1, 0]), Some((1, 1)));
+        assert_eq!(try_max_prod_fast_int_positive([0u32, 2, 3, 0]), Some((1, 2)));
+
+        // the infallible version can't tell these apart
+        assert_eq!(max_prod_fast_int([0u32, 0, 0]), max_prod_fast_int([1u32, 0, 0]));
+    }
+
+    #[test]
+    fn test_check_range() {
+        assert_eq!(check_range((2, 2)), Ok(()));
+        assert_eq!(check_range((1, 4)), Ok(()));
+        assert_eq!(check_range((3, 1)), Err(MaxProdError::InvalidRange { start: 3, end: 1 }));
+        assert_eq!(MaxProdError::InvalidRange { start: 3, end: 1 }.to_string(), "invalid range 3 .. 1");
+    }
+
+    #[test]
+    fn test_checked_prod() {
+        let a = [2u32, 65536, 32768, 3];
+        assert_eq!(checked_prod(a, 0, 1), Some(131072));
+        assert_eq!(checked_prod(a, 1, 1), Some(65536));
+        assert_eq!(checked_prod(a, 1, 2), Some(1 << 31));
+        assert_eq!(checked_prod(a, 1, 3), None);
+        assert_eq!(checked_prod(a, 0, 2), None);
+        assert_eq!(checked_prod([u32::MAX, 1], 0, 1), Some(u32::MAX));
+    }
+
+    #[test]
+    fn test_brute_force_basic() {
+        let a = vec![1u32, 2, 3, 4];
+        let max = max_prod_fast_int(&a);
+        assert_eq!(max, (0, 3));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn test_brute_force_basic2() {
+        let a = vec![0u32, 2, 3, 4];
+        let max = max_prod_fast_int(&a);
+        assert_eq!(max, (1, 3));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn test_brute_force_basic3() {
+        let a = vec![0, 1u32, 0, 0];
+        let max = max_prod_fast_int(&a);
+        assert_eq!(max, (1, 1));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn test_brute_force_basic4() {
+        let a = vec![0, 1u32, 0, 1];
+        let max = max_prod_fast_int(&a);
+        assert_eq!(max, (1, 1));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn test_brute_force_basic5() {
+        let a = vec![0, 1u32, 0, 7, 0, 3];
+        let max = max_prod_fast_int(&a);
+        assert_eq!(max, (3, 3));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn test_brute_force_basic6() {
+        let a = vec![4u32];
+        let max = max_prod_fast_int(&a);
+        assert_eq!(max, (0, 0));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn test_integer1() {
+        let a: Vec<u128> = vec![4, 9, 7, 3, 4, 8, 9, 2, 0, 3, 9, 6, 9, 2, 0, 5, 7, 2,
+                                5, 8, 9, 7, 1, 5, 2, 8, 3, 7, 5, 2, 7, 8, 3, 1, 5, 4, 6, 1, 2,
+                                5, 3, 2, 4, 4, 4, 3, 1, 9, 4, 7, 9, 4, 5, 7, 5, 5, 7, 5, 8, 9];
+        let max = max_prod_fast_int(&a);
+        assert_eq!(max, (15, 59));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn test_with_value_int() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let mut a: Vec<u128> = Uniform::new_inclusive(0, 10).sample_iter(&mut rng).take(50).collect();
+
+            let n = a.len();
+            a[Uniform::new(0, n).sample(&mut rng)] = 0;
+
+            for (p, i, j) in [max_prod_fast_int_with_value(&a), max_prod_brute_force_with_value(&a), max_prod_brute_force_improved_with_value(&a)] {
+                assert_eq!(p, prod(&a, i, j));
+            }
+            let (_, i, j) = max_prod_fast_int_with_value(&a);
+            assert_eq!((i, j), max_prod_fast_int(&a));
+        }
+    }
+
+    #[test]
+    fn test_with_value_zeros() {
+        let a = vec![0u32, 0, 0];
+        assert_eq!(max_prod_fast_int_with_value(&a), (0, 0, 0));
+        assert_eq!(max_prod_brute_force_with_value(&a), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_real_brute_force_01() {
+        let mut rng = test_rng();
+        let farr: Vec<f32> = Uniform::new(0.0, 1.0).sample_iter(&mut rng).take(20).collect();
+        println!("F = {:?}", farr);
+        let (i, j) = max_prod_brute_force(&farr[..]);
+        let p = prod(&farr, i, j);
+        let m = farr.into_iter().fold(0.0, |a, b| b.max(a));
+        println!("F[{i} .. {j}] = {} (max = {m})", p);
+        assert_eq!(p, m);
+    }
+}
@@ -0,0 +1,62 @@
+use ndarray::ArrayView1;
+
+use crate::real::{max_prod_fast_real, max_prod_fast_real_iter};
+
+/// [`max_prod_fast_real`](crate::max_prod_fast_real) on an `ndarray` view, without copying
+/// it into a `Vec`.
+///
+/// A contiguous view is passed on as a slice, a strided one, e.g. every other element or a
+/// column of a matrix, is walked element by element. The indices are positions in the view.
+///
+/// ```
+/// use ndarray::{array, s};
+/// use max_prod::max_prod_fast_real_ndarray;
+///
+/// let a = array![2.0, 0.0, 3.0, 0.0, 0.1, 0.0, 4.0];
+/// assert_eq!(max_prod_fast_real_ndarray(&a.slice(s![..;2])), (0, 1));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_ndarray(view: &ArrayView1<f64>) -> (usize, usize) {
+    if let Some(arr) = view.as_slice() {
+        return max_prod_fast_real(arr);
+    }
+
+    if view.is_empty() {
+        return (0, 0);
+    }
+
+    let (_, i, j) = max_prod_fast_real_iter(view.iter().copied()).unwrap();
+    (i, j)
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{s, Array1, Array2};
+    use rand::distributions::{Distribution, Uniform};
+
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_strided() {
+        let mut rng = test_rng();
+        for n in [0, 1, 2, 3, 10, 100] {
+            let a: Array1<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            let strided = a.slice(s![..;2]);
+            assert!(n < 3 || strided.as_slice().is_none());
+
+            let copied: Vec<f64> = strided.iter().copied().collect();
+            assert_eq!(max_prod_fast_real_ndarray(&strided), max_prod_fast_real(&copied));
+            assert_eq!(max_prod_fast_real_ndarray(&a.view()), max_prod_fast_real(a.as_slice().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_column() {
+        let m = Array2::from_shape_vec((4, 2), vec![2.0, 0.0, 0.25, 1.0, 3.0, 1.0, 4.0, 1.0]).unwrap();
+        assert_eq!(max_prod_fast_real_ndarray(&m.column(0)), (2, 3));
+        assert_eq!(max_prod_fast_real_ndarray(&m.column(1)), (1, 1));
+        assert_eq!(max_prod_fast_real_ndarray(&m.slice(s![..;-1, 0])), (0, 1));
+    }
+}
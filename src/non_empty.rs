@@ -0,0 +1,119 @@
+use core::ops::Deref;
+
+use crate::error::MaxProdError;
+
+/// A slice with at least one element.
+///
+/// Every algorithm takes `impl AsRef<[T]>`, so a `NonEmptySlice` can be passed wherever a
+/// slice can. The `try_` algorithms check for an empty input through it, once the check
+/// passed the algorithms behind them can rely on there being a first element. The
+/// `_non_empty` algorithms take it directly and have no empty case to report.
+///
+/// ```
+/// use max_prod::{max_prod_fast_int, max_prod_fast_int_non_empty, MaxProdError, NonEmptySlice};
+///
+/// let arr = [2u32, 0, 3, 4];
+/// let arr = NonEmptySlice::try_from(&arr[..]).unwrap();
+/// assert_eq!(max_prod_fast_int(arr), (2, 3));
+/// assert_eq!(max_prod_fast_int_non_empty(arr), (2, 3));
+///
+/// let empty: &[u32] = &[];
+/// assert_eq!(NonEmptySlice::try_from(empty), Err(MaxProdError::Empty));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonEmptySlice<'a, T>(&'a [T]);
+
+impl<'a, T> NonEmptySlice<'a, T> {
+    /// The underlying slice, with the lifetime of the original one instead of `self`.
+    #[must_use]
+    pub fn as_slice(&self) -> &'a [T] {
+        self.0
+    }
+}
+
+impl<'a, T> TryFrom<&'a [T]> for NonEmptySlice<'a, T> {
+    type Error = MaxProdError;
+
+    /// Fails with [`MaxProdError::Empty`] for an empty slice.
+    fn try_from(arr: &'a [T]) -> Result<Self, Self::Error> {
+        if arr.is_empty() {
+            Err(MaxProdError::Empty)
+        } else {
+            Ok(NonEmptySlice(arr))
+        }
+    }
+}
+
+impl<T> AsRef<[T]> for NonEmptySlice<'_, T> {
+    fn as_ref(&self) -> &[T] {
+        self.0
+    }
+}
+
+impl<T> Deref for NonEmptySlice<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use crate::max_prod::{
+        max_prod_brute_force,
+        max_prod_brute_force_improved,
+        max_prod_brute_force_improved_non_empty,
+        max_prod_brute_force_non_empty,
+        max_prod_fast_int,
+        max_prod_fast_int_non_empty,
+        try_max_prod_fast_int,
+    };
+    use crate::real::{max_prod_fast_real, max_prod_fast_real_non_empty};
+    use crate::test_rng::test_rng;
+    use super::*;
+
+    #[test]
+    fn test_try_from_empty() {
+        let empty: &[f64] = &[];
+        assert_eq!(NonEmptySlice::try_from(empty), Err(MaxProdError::Empty));
+        assert_eq!(NonEmptySlice::<u32>::try_from(&[][..]).unwrap_err().to_string(), "input is empty");
+    }
+
+    #[test]
+    fn test_try_from() {
+        let a = vec![0u32, 2, 3, 0, 5];
+        let s = NonEmptySlice::try_from(a.as_slice()).unwrap();
+
+        assert_eq!(s.len(), 5);
+        assert_eq!(s.as_slice(), &a[..]);
+        assert_eq!(s[4], 5);
+        assert_eq!(max_prod_brute_force(s), (1, 2));
+        assert_eq!(try_max_prod_fast_int(s), Ok((1, 2)));
+
+        let single = NonEmptySlice::try_from(&a[3..4]).unwrap();
+        assert_eq!(max_prod_brute_force(single), (0, 0));
+    }
+
+    #[test]
+    fn test_non_empty_algorithms() {
+        let mut rng = test_rng();
+
+        for _ in 0..200 {
+            let len = rng.gen_range(1..30);
+            let a: Vec<u32> = (0..len).map(|_| rng.gen_range(0..4)).collect();
+            let s = NonEmptySlice::try_from(a.as_slice()).unwrap();
+
+            assert_eq!(max_prod_fast_int_non_empty(s), max_prod_fast_int(&a));
+            assert_eq!(max_prod_brute_force_non_empty(s), max_prod_brute_force(&a));
+            assert_eq!(max_prod_brute_force_improved_non_empty(s), max_prod_brute_force_improved(&a));
+
+            let r: Vec<f64> = a.iter().map(|&x| f64::from(x) * 0.5).collect();
+            let s = NonEmptySlice::try_from(r.as_slice()).unwrap();
+            assert_eq!(max_prod_fast_real_non_empty(s), max_prod_fast_real(&r));
+            assert_eq!(max_prod_brute_force_non_empty(s), max_prod_brute_force(&r));
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use num::traits::Num;
+use num::traits::real::Real;
+use rayon::prelude::*;
+
+use crate::int::MaxProdInt;
+use crate::max_prod::{best_or_default, consider, prod};
+use crate::real::{combine_compressed, compress_dual_from, max_prod_fast_real_with_value, nan_as_zero, widest_inf_run};
+use crate::top_k::top_k_max_prod_fast_int;
+
+/// Arrays shorter than this per thread aren't worth splitting.
+const MIN_CHUNK_LEN: usize = 1 << 14;
+
+/// Parallel version of [`max_prod_brute_force`](crate::max_prod_brute_force).
+///
+/// The outer loop over the start index is split across the rayon thread pool, every start
+/// computes its local best and the results are reduced preferring the smaller start on
+/// ties. This yields exactly the same range as the serial version.
+///
+/// ```
+/// use max_prod::max_prod_brute_force_par;
+///
+/// assert_eq!(max_prod_brute_force_par(&[2u32, 0, 3, 4]), (2, 3));
+/// ```
+#[must_use]
+#[allow(clippy::needless_range_loop)] // the triple loop of the brute force, on purpose
+pub fn max_prod_brute_force_par<T: Num + Copy + PartialOrd + Send + Sync>(arr: &[T]) -> (usize, usize) {
+    let n = arr.len();
+
+    let best = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let mut best = None;
+
+            for j in i..n {
+                let mut prod = T::one();
+
+                for k in i..=j {
+                    prod = prod * arr[k];
+                }
+
+                consider(&mut best, prod, i, j);
+            }
+
+            best
+        })
+        .reduce(
+            || None,
+            |a, b| match (a, b) {
+                (Some(x), Some(y)) => Some(if y.0 > x.0 || (y.0 == x.0 && y.1 < x.1) { y } else { x }),
+                (x, None) | (None, x) => x,
+            },
+        );
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    (i, j)
+}
+
+/// Parallel version of [`max_prod_fast_real`](crate::max_prod_fast_real).
+///
+/// The array is split into one chunk per thread, which are compressed into segments of
+/// numbers smaller and larger than one in parallel. Chunks are only split right before an
+/// element larger than one that follows an element smaller than one, there a new segment
+/// starts anyway, so every chunk yields exactly the segments the serial version would.
+/// The trailing small segment of each chunk is put back between the chunks and the
+/// segments are merged serially, so the result is exactly the same as the serial one.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_par;
+///
+/// assert_eq!(max_prod_fast_real_par(&[0.5, 2.0, 3.0, 0.1]), (1, 2));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_par<T: Real + Copy + Send + Sync>(arr: &[T]) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_real_par_with_value(arr);
+    (i, j)
+}
+
+/// Like [`max_prod_fast_real_par`] but also returns the product of the winning range.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_par_with_value;
+///
+/// assert_eq!(max_prod_fast_real_par_with_value(&[0.5, 2.0, 3.0, 0.1]), (6.0, 1, 2));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_par_with_value<T: Real + Copy + Send + Sync>(arr: &[T]) -> (T, usize, usize) {
+    let chunks = rayon::current_num_threads().min(arr.len() / MIN_CHUNK_LEN);
+    max_prod_fast_real_par_chunks(arr, chunks)
+}
+
+fn max_prod_fast_real_par_chunks<T: Real + Copy + Send + Sync>(arr: &[T], chunks: usize) -> (T, usize, usize) {
+    let splits = chunk_splits(arr, chunks);
+
+    if splits.len() <= 2 {
+        return max_prod_fast_real_with_value(arr);
+    }
+
+    // the checks of `max_prod_fast_real` that come before the compression
+    if arr.par_iter().any(|&x| x > T::max_value()) {
+        let (i, j) = widest_inf_run(arr).unwrap();
+        return (arr[i], i, j);
+    }
+    if arr.par_iter().all(|&x| x >= T::one()) {
+        let end = arr.iter().rposition(|&x| x > T::one()).unwrap_or(0);
+        return (prod(arr, 0, end), 0, end);
+    }
+
+    let parts: Vec<_> = splits
+        .par_windows(2)
+        .map(|w| {
+            let (a, b) = (w[0], w[1]);
+            let chunk = &arr[a..b];
+
+            // only the first chunk can start with numbers smaller than one, which are
+            // skipped like in `compress_dual`
+            let first = match chunk.iter().position(|&x| nan_as_zero(x) >= T::one()) {
+                Some(first) => first,
+                None => return (Vec::new(), None),
+            };
+
+            let (segments, tail) = compress_dual_from(chunk, first, nan_as_zero);
+            let offset = |(p, i, j): (T, usize, usize)| (p, i + a, j + a);
+            (segments.into_iter().map(offset).collect::<Vec<_>>(), tail.map(offset))
+        })
+        .collect();
+
+    let mut compressed = Vec::new();
+    let last = parts.len() - 1;
+    for (k, (segments, tail)) in parts.into_iter().enumerate() {
+        compressed.extend(segments);
+        if k < last {
+            compressed.extend(tail);
+        }
+    }
+
+    combine_compressed(compressed).unwrap()
+}
+
+/// Start of every chunk plus `arr.len()` at the end. Each split is moved forward from its
+/// even position to the next element larger than one that follows an element smaller than one.
+fn chunk_splits<T: Real + Copy>(arr: &[T], chunks: usize) -> Vec<usize> {
+    let n = arr.len();
+    let mut splits = vec![0];
+
+    for k in 1..chunks {
+        let mut m = (k * n / chunks).max(splits[splits.len() - 1] + 1);
+
+        while m < n && !(nan_as_zero(arr[m - 1]) < T::one() && nan_as_zero(arr[m]) > T::one()) {
+            m += 1;
+        }
+
+        if m >= n {
+            break;
+        }
+        splits.push(m);
+    }
+
+    splits.push(n);
+    splits
+}
+
+/// Runs [`top_k_max_prod_fast_int`](crate::top_k_max_prod_fast_int) on every array of
+/// `arrays` in parallel.
+///
+/// The top `k` ranges of `arrays[m]` are at index `m` of the result, no matter in which
+/// order the threads finish.
+///
+/// ```
+/// use max_prod::top_k_max_prod_batch;
+///
+/// let arrays = vec![vec![2u32, 0, 3, 4, 0, 5], vec![0, 0], vec![3]];
+/// assert_eq!(top_k_max_prod_batch(&arrays, 2), vec![vec![(2, 3), (5, 5)], vec![], vec![(0, 0)]]);
+/// ```
+#[must_use]
+pub fn top_k_max_prod_batch<T: MaxProdInt + Send + Sync>(arrays: &[Vec<T>], k: usize) -> Vec<Vec<(usize, usize)>> {
+    arrays.par_iter().map(|arr| top_k_max_prod_fast_int(arr, k)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use rand::Rng;
+
+    use crate::max_prod::max_prod_brute_force;
+    use crate::real::max_prod_fast_real_with_value;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_random_par_int() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let a: Vec<u128> = Uniform::new_inclusive(0, 5).sample_iter(&mut rng).take(50).collect();
+            assert_eq!(max_prod_brute_force_par(&a), max_prod_brute_force(&a));
+        }
+    }
+
+    #[test]
+    fn test_random_par_real() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(60).collect();
+            assert_eq!(max_prod_brute_force_par(&a), max_prod_brute_force(&a));
+        }
+    }
+
+    #[test]
+    fn test_par_ties() {
+        let a = vec![1u32, 1, 1, 1];
+        assert_eq!(max_prod_brute_force_par(&a), max_prod_brute_force(&a));
+
+        let a = vec![0u32, 0, 0];
+        assert_eq!(max_prod_brute_force_par(&a), (0, 0));
+
+        let a: Vec<u32> = vec![];
+        assert_eq!(max_prod_brute_force_par(&a), max_prod_brute_force(&a));
+    }
+
+    fn assert_same_as_serial(a: &[f64], chunks: usize) {
+        let (p, i, j) = max_prod_fast_real_with_value(a);
+        let (q, k, l) = max_prod_fast_real_par_chunks(a, chunks);
+        assert_eq!((k, l), (i, j), "{a:?} {chunks}");
+        assert_eq!(q.to_bits(), p.to_bits(), "{a:?} {chunks}");
+    }
+
+    #[test]
+    fn test_par_real_straddling() {
+        // the split of two chunks is moved to index 4, the best range is [1 .. 6]
+        let a = vec![0.1, 3.0, 0.9, 0.5, 4.0, 0.8, 5.0, 0.1];
+        assert_eq!(chunk_splits(&a, 2), vec![0, 4, 8]);
+        assert_eq!(max_prod_fast_real_par_chunks(&a, 2), max_prod_fast_real_with_value(&a));
+        assert_eq!(max_prod_fast_real_par_chunks(&a, 2).1, 1);
+        assert_eq!(max_prod_fast_real_par_chunks(&a, 2).2, 6);
+
+        // only numbers smaller than one in the first chunk
+        let a = vec![0.5, 0.2, 0.3, 0.5, 2.0, 0.5, 3.0];
+        assert_same_as_serial(&a, 2);
+        assert_same_as_serial(&a, 3);
+    }
+
+    #[test]
+    fn test_par_real_no_split() {
+        // no element larger than one follows one smaller than one
+        let a = vec![2.0, 3.0, 0.5, 0.25];
+        assert_eq!(chunk_splits(&a, 4), vec![0, 4]);
+        assert_same_as_serial(&a, 4);
+
+        assert_eq!(max_prod_fast_real_par(&[0.5f64]), (0, 0));
+        assert_eq!(max_prod_fast_real_par::<f64>(&[]), (0, 0));
+    }
+
+    #[test]
+    fn test_random_par_fast_real() {
+        let mut rng = test_rng();
+        let special = [0.0, 1.0, f64::NAN, f64::INFINITY];
+
+        for _ in 0..500 {
+            let n = rng.gen_range(1..200);
+            let mut a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            for x in &mut a {
+                if rng.gen_bool(0.05) {
+                    *x = special[rng.gen_range(0..special.len())];
+                }
+            }
+
+            for chunks in [2, 3, 7, 16] {
+                assert_same_as_serial(&a, chunks);
+            }
+        }
+    }
+
+    #[test]
+    fn test_par_real_exact_ties() {
+        let values = [0.0, 0.5, 1.0, 2.0];
+
+        for n in 1..=7 {
+            for k in 0..values.len().pow(n as u32) {
+                let a: Vec<f64> = (0..n).map(|d| values[k / values.len().pow(d as u32) % values.len()]).collect();
+                let (_, i, j) = max_prod_fast_real_par_chunks(&a, 3);
+                assert_eq!((i, j), max_prod_brute_force(&a), "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_par_fast_real_large() {
+        let mut rng = test_rng();
+        let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(4 * MIN_CHUNK_LEN).collect();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        assert_eq!(pool.install(|| max_prod_fast_real_par_with_value(&a)), max_prod_fast_real_with_value(&a));
+    }
+
+    #[test]
+    fn test_top_k_batch() {
+        let mut rng = test_rng();
+        let mut arrays: Vec<Vec<u64>> = (0..8)
+            .map(|_| {
+                let n = rng.gen_range(0..40);
+                Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(n).collect()
+            })
+            .collect();
+        arrays.push(vec![]);
+        arrays.push(vec![0, 0, 0]);
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        for k in [0, 1, 3, 10] {
+            let serial: Vec<_> = arrays.iter().map(|a| top_k_max_prod_fast_int(a, k)).collect();
+            assert_eq!(pool.install(|| top_k_max_prod_batch(&arrays, k)), serial, "k = {k}");
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::traits::Num;
+
+/// Precomputed prefix products for answering [`prod`](crate::prod) queries in O(1).
+///
+/// Zeros are counted separately and left out of the prefix products, so a range
+/// containing a zero is answered with zero and any other range with one division.
+/// This needs the product of the whole array (without its zeros) to fit into `T`,
+/// for floats the division can also add a small rounding error.
+///
+/// ```
+/// use max_prod::PrefixProduct;
+///
+/// let p = PrefixProduct::new([2u32, 3, 0, 4]);
+/// assert_eq!(p.query(0, 1), 6);
+/// assert_eq!(p.query(1, 3), 0);
+/// assert_eq!(p.len(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixProduct<T> {
+    // prefix[k] is the product of the non-zero elements of arr[..k]
+    prefix: Vec<T>,
+    // zeros[k] is the number of zeros in arr[..k]
+    zeros: Vec<usize>,
+}
+
+impl<T: Num + Copy> PrefixProduct<T> {
+    pub fn new(arr: impl AsRef<[T]>) -> Self {
+        let arr = arr.as_ref();
+        let mut prefix = vec![T::one(); arr.len() + 1];
+        let mut zeros = vec![0; arr.len() + 1];
+
+        for (k, &x) in arr.iter().enumerate() {
+            if x == T::zero() {
+                prefix[k + 1] = prefix[k];
+                zeros[k + 1] = zeros[k] + 1;
+            } else {
+                prefix[k + 1] = prefix[k] * x;
+                zeros[k + 1] = zeros[k];
+            }
+        }
+
+        PrefixProduct { prefix, zeros }
+    }
+
+    /// The product of `arr[i..=j]`, panics if the range is out of bounds.
+    pub fn query(&self, i: usize, j: usize) -> T {
+        assert!(i <= j && j < self.len(), "range {} .. {} out of bounds", i, j);
+
+        if self.zeros[j + 1] > self.zeros[i] {
+            T::zero()
+        } else {
+            self.prefix[j + 1] / self.prefix[i]
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::prod;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_prefix_basic() {
+        let arr = [2u32, 0, 3, 4, 1, 5];
+        let p = PrefixProduct::new(arr);
+
+        assert_eq!(p.len(), 6);
+        assert_eq!(p.query(0, 0), 2);
+        assert_eq!(p.query(0, 2), 0);
+        assert_eq!(p.query(1, 1), 0);
+        assert_eq!(p.query(2, 5), 60);
+        assert_eq!(p.query(3, 4), 4);
+
+        assert!(PrefixProduct::<u32>::new([]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_prefix_out_of_bounds() {
+        PrefixProduct::new([1u32, 2]).query(1, 2);
+    }
+
+    #[test]
+    fn test_random_prefix_int() {
+        let mut rng = test_rng();
+        for _ in 0..100 {
+            let a: Vec<u128> = Uniform::new_inclusive(0, 3).sample_iter(&mut rng).take(40).collect();
+            let p = PrefixProduct::new(&a);
+
+            for i in 0..a.len() {
+                for j in i..a.len() {
+                    assert_eq!(p.query(i, j), prod(&a, i, j));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_prefix_real() {
+        let mut rng = test_rng();
+        for _ in 0..100 {
+            let mut a: Vec<f64> = Uniform::new(0.5, 2.0).sample_iter(&mut rng).take(40).collect();
+            let k = rng.gen_range(0..a.len());
+            a[k] = 0.0;
+            let p = PrefixProduct::new(&a);
+
+            for i in 0..a.len() {
+                for j in i..a.len() {
+                    let expected = prod(&a, i, j);
+                    assert!((p.query(i, j) - expected).abs() <= 1e-9 * expected);
+                }
+            }
+        }
+    }
+}
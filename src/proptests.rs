@@ -0,0 +1,26 @@
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::max_prod::{max_prod_brute_force, max_prod_brute_force_improved, max_prod_fast_int};
+use crate::real::max_prod_fast_real;
+
+fn int_array() -> impl Strategy<Value = Vec<u128>> {
+    vec(prop_oneof![1 => Just(0u128), 4 => 1u128..=10], 1..50)
+}
+
+fn real_array() -> impl Strategy<Value = Vec<f64>> {
+    vec(0.0f64..2.0, 1..100)
+}
+
+proptest! {
+    #[test]
+    fn prop_fast_int_matches_brute_force(a in int_array()) {
+        prop_assert_eq!(max_prod_fast_int(&a), max_prod_brute_force(&a));
+        prop_assert_eq!(max_prod_fast_int(&a), max_prod_brute_force_improved(&a));
+    }
+
+    #[test]
+    fn prop_fast_real_matches_brute_force(a in real_array()) {
+        prop_assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a));
+    }
+}
@@ -0,0 +1,117 @@
+use num::traits::Num;
+
+use crate::real::{combine_compressed, compress_dual_with};
+
+/// Fast algorithm for exact non-negative numbers such as [`Ratio`](num::rational::Ratio).
+///
+/// Works like [`max_prod_fast_real`](crate::max_prod_fast_real) but only needs ordering,
+/// so values are split at exactly one and products near ties are compared without any
+/// rounding. Of several ranges with exactly the same product the one with the smallest
+/// start and then the smallest end wins, like in the brute force.
+///
+/// ```
+/// use num::rational::Ratio;
+/// use max_prod::max_prod_fast_rational;
+///
+/// let a = [Ratio::new(1, 2), Ratio::new(3, 1), Ratio::new(2, 3), Ratio::new(4, 1)];
+/// assert_eq!(max_prod_fast_rational(a), (1, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_rational<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_rational_with_value(arr);
+    (i, j)
+}
+
+/// Like [`max_prod_fast_rational`] but also returns the product of the winning range.
+///
+/// ```
+/// use num::rational::Ratio;
+/// use max_prod::max_prod_fast_rational_with_value;
+///
+/// let a = [Ratio::new(1, 2), Ratio::new(3, 1), Ratio::new(2, 3), Ratio::new(4, 1)];
+/// assert_eq!(max_prod_fast_rational_with_value(a), (Ratio::from_integer(8), 1, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_rational_with_value<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>) -> (T, usize, usize) {
+    let arr = arr.as_ref();
+
+    if arr.is_empty() {
+        return (T::zero(), 0, 0);
+    }
+
+    combine_compressed(compress_dual_with(arr, |x| x).unwrap()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+
+    use num::rational::Ratio;
+
+    use crate::max_prod::{max_prod_brute_force_improved, max_prod_brute_force_improved_with_value};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn r(n: i64, d: i64) -> Ratio<i64> {
+        Ratio::new(n, d)
+    }
+
+    #[test]
+    fn test_rational_straddling_one() {
+        // 2 * 3/4 * 4/3 is exactly 2, so the single 2 wins like in the brute force
+        let arr = [r(1, 2), r(2, 1), r(3, 4), r(4, 3)];
+        assert_eq!(max_prod_fast_rational(arr), (1, 1));
+        assert_eq!(max_prod_fast_rational(arr), max_prod_brute_force_improved(arr));
+
+        let arr = [r(1, 2), r(2, 1), r(3, 4), r(5, 3)];
+        assert_eq!(max_prod_fast_rational_with_value(arr), (r(5, 2), 1, 3));
+
+        let arr = [r(3, 2), r(2, 3), r(3, 2)];
+        assert_eq!(max_prod_fast_rational_with_value(arr), (r(3, 2), 0, 0));
+
+        let arr = [r(1, 1), r(1, 2), r(1, 1), r(3, 1), r(1, 1)];
+        assert_eq!(max_prod_fast_rational_with_value(arr), (r(3, 1), 2, 3));
+    }
+
+    #[test]
+    fn test_rational_ties() {
+        let arr = [r(1, 1), r(0, 1), r(2, 1), r(0, 1), r(2, 1)];
+        assert_eq!(max_prod_fast_rational(arr), (2, 2));
+
+        let arr = [r(3, 2), r(1, 2), r(2, 1), r(1, 2), r(2, 1)];
+        assert_eq!(max_prod_fast_rational(arr), (2, 2));
+        assert_eq!(max_prod_fast_rational(arr), max_prod_brute_force_improved(arr));
+    }
+
+    #[test]
+    fn test_rational_small() {
+        let arr = [r(1, 2), r(1, 3)];
+        assert_eq!(max_prod_fast_rational_with_value(arr), (r(1, 2), 0, 0));
+
+        let arr: [Ratio<i64>; 0] = [];
+        assert_eq!(max_prod_fast_rational(arr), (0, 0));
+    }
+
+    #[test]
+    fn test_random_rational() {
+        let mut rng = test_rng();
+        let dist = Uniform::new_inclusive(0, 4);
+
+        for _ in 0..500 {
+            let n = rng.gen_range(1..10);
+            let arr: Vec<Ratio<i64>> = (0..n)
+                .map(|_| {
+                    let a = rng.sample(dist);
+                    let b = rng.sample(dist);
+                    if b == 0 { r(0, 1) } else { r(a, b) }
+                })
+                .collect();
+
+            // exact ties are common here
+            assert_eq!(max_prod_fast_rational_with_value(&arr), max_prod_brute_force_improved_with_value(&arr), "{arr:?}");
+            assert_eq!(max_prod_fast_rational(&arr), max_prod_brute_force_improved(&arr), "{arr:?}");
+        }
+    }
+}
@@ -0,0 +1,1496 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::traits::Num;
+use num::traits::real::Real;
+
+use crate::error::MaxProdError;
+use crate::max_prod::{best_or_default, check_range, max_prod_kadane_with_value};
+use crate::non_empty::NonEmptySlice;
+use crate::result::MaxProdResult;
+use crate::span::Span;
+
+/// `NaN` can't be ordered against one and `-inf` is negative, so `max_prod_fast_real`
+/// treats both like a zero, which acts as a hard separator: no range containing
+/// one of them can win.
+pub(crate) fn nan_as_zero<T: Real + Copy>(x: T) -> T {
+    if x.partial_cmp(&x).is_none() || x < T::min_value() {
+        T::zero()
+    } else {
+        x
+    }
+}
+
+/// The product of `arr[start..=end]` together with `start` and `end`.
+pub type Segment<T> = (T, usize, usize);
+
+/// Splits `arr` into alternating segments of numbers smaller and larger than one, the first
+/// step of [`max_prod_fast_real`].
+///
+/// Each segment is a tuple `(product, start, end)` of the inclusive range
+/// `arr[start..=end]` and its product.
+///
+/// - The segments are sorted and cover every index: the first one starts at zero, every
+///   other one right after the end of the previous one, and the last one ends at
+///   `arr.len() - 1`.
+/// - They alternate between small and large segments. A small segment starts with an
+///   element smaller than one and has no element larger than one, its product is smaller
+///   than one. A large segment has no element smaller than one, its product is at least
+///   one.
+/// - Ones don't change a product. Ones right before or after a large segment are part of
+///   it, the other ones are part of the small segment around them. Only the first one
+///   after the leading elements smaller than one starts a large segment right away.
+///
+/// `max_prod_fast_real` only merges the large segments and the small ones between them,
+/// the best range never starts or ends in a small segment.
+///
+/// Fails with [`MaxProdError::Empty`] for an empty slice and with
+/// [`MaxProdError::Negative`] if an element is negative, a negative factor flips the sign
+/// of a product and doesn't fit into either kind of segment. `NaN`s can't be ordered
+/// against one, replace them first, `max_prod_fast_real` treats them like zeros.
+///
+/// ```
+/// use max_prod::{compress_dual, MaxProdError};
+///
+/// let arr = [0.5, 2.0, 3.0, 1.0, 0.5, 0.25, 1.0, 4.0, 0.5];
+/// let segments = compress_dual(&arr).unwrap();
+/// assert_eq!(segments, vec![(0.5, 0, 0), (6.0, 1, 3), (0.125, 4, 5), (4.0, 6, 7), (0.5, 8, 8)]);
+///
+/// assert_eq!(compress_dual(&[0.5, 2.0, -3.0]), Err(MaxProdError::Negative { index: 2 }));
+/// ```
+pub fn compress_dual<T: Num + Copy + PartialOrd>(arr: &[T]) -> Result<Vec<Segment<T>>, MaxProdError> {
+    if let Some(index) = first_negative(arr, |x| x) {
+        return Err(MaxProdError::Negative { index });
+    }
+
+    let n = arr.len();
+    let product = |i: usize, j: usize| arr[i..=j].iter().fold(T::one(), |p, &x| p * x);
+    let inner = compress_dual_with(arr, |x| x)?;
+
+    // every element is smaller than one, the only segment is a small one
+    if inner[0].0 < T::one() {
+        return Ok(vec![(product(0, n - 1), 0, n - 1)]);
+    }
+
+    let mut segments = Vec::with_capacity(inner.len() + 2);
+
+    // the leading elements smaller than one
+    if inner[0].1 > 0 {
+        segments.push((product(0, inner[0].1 - 1), 0, inner[0].1 - 1));
+    }
+
+    for (p, i, j) in inner {
+        // only ones are left out in between, after a large segment
+        if let Some(last) = segments.last_mut() {
+            last.2 = i - 1;
+        }
+        segments.push((p, i, j));
+    }
+
+    // the trailing ones belong to the last large segment, the rest is a small one
+    let last = segments.len() - 1;
+    match (segments[last].2 + 1..n).find(|&k| arr[k] < T::one()) {
+        Some(k) => {
+            segments[last].2 = k - 1;
+            segments.push((product(k, n - 1), k, n - 1));
+        }
+        None => segments[last].2 = n - 1,
+    }
+
+    Ok(segments)
+}
+
+/// The segments `max_prod_fast_real` merges: like [`compress_dual`] without the small
+/// segments at either end and the ones after a large segment, and every element is
+/// passed through `sanitize` first.
+///
+/// `max_prod_fast_real` maps `NaN` and `-inf` to zero, `|x| x` keeps the elements as they
+/// are. Only if every element is smaller than one the result is the single largest element
+/// instead. The elements must not be negative after `sanitize`, debug builds check this.
+pub(crate) fn compress_dual_with<T: Num + Copy + PartialOrd>(arr: &[T], sanitize: impl Fn(T) -> T) -> Result<Vec<Segment<T>>, MaxProdError> {
+    debug_assert!(first_negative(arr, &sanitize).is_none(), "compress_dual on a negative element");
+    compress_dual_iter(NonEmptySlice::try_from(arr)?.iter().copied(), sanitize)
+}
+
+fn first_negative<T: Num + Copy + PartialOrd>(arr: &[T], sanitize: impl Fn(T) -> T) -> Option<usize> {
+    arr.iter().position(|&x| sanitize(x) < T::zero())
+}
+
+/// Like [`compress_dual`] but every segment also carries the cumulative product of
+/// `arr[0..=end]`, e.g. to plot how the product develops along the array.
+///
+/// The cumulative product is the one of the previous segment times the product of this
+/// one.
+///
+/// ```
+/// use max_prod::compress_dual_with_cumulative;
+///
+/// let arr = [0.5, 2.0, 3.0, 1.0, 0.5, 0.25, 1.0, 4.0];
+/// let segments = compress_dual_with_cumulative(&arr).unwrap();
+/// assert_eq!(segments, vec![(0.5, 0, 0, 0.5), (6.0, 1, 3, 3.0), (0.125, 4, 5, 0.375), (4.0, 6, 7, 1.5)]);
+/// ```
+pub fn compress_dual_with_cumulative<T: Num + Copy + PartialOrd>(arr: &[T]) -> Result<Vec<(T, usize, usize, T)>, MaxProdError> {
+    let mut cumulative = T::one();
+
+    Ok(compress_dual(arr)?
+        .into_iter()
+        .map(|(p, i, j)| {
+            cumulative = cumulative * p;
+            (p, i, j, cumulative)
+        })
+        .collect())
+}
+
+/// `compress_dual` over the elements of an iterator, for inputs that aren't a slice.
+pub(crate) fn compress_dual_iter<T: Num + Copy + PartialOrd>(iter: impl Iterator<Item = T>, sanitize: impl Fn(T) -> T) -> Result<Vec<Segment<T>>, MaxProdError> {
+    let mut compressed = Vec::new();
+    compress_dual_iter_into(iter, sanitize, &mut compressed)?;
+    Ok(compressed)
+}
+
+/// `compress_dual_iter` writing the segments into `compressed`, which is cleared first.
+fn compress_dual_iter_into<T: Num + Copy + PartialOrd>(iter: impl Iterator<Item = T>, sanitize: impl Fn(T) -> T, compressed: &mut Vec<Segment<T>>) -> Result<(), MaxProdError> {
+    compressed.clear();
+    let mut iter = iter.enumerate();
+
+    let mut tmp_max = T::zero();
+    let mut tmp_max_idx = 0;
+    let mut n = 0;
+
+    let first = loop {
+        let Some((i, x)) = iter.next() else {
+            break None;
+        };
+
+        n += 1;
+
+        if sanitize(x) >= T::one() {
+            break Some((i, x));
+        }
+
+        if x > tmp_max {
+            tmp_max = x;
+            tmp_max_idx = i;
+        }
+    };
+
+    // only if every element is smaller than one can the best single element of this
+    // leading run win, otherwise arr[start] alone has a product of at least one
+    let Some((start, x)) = first else {
+        if n == 0 {
+            return Err(MaxProdError::Empty);
+        }
+
+        compressed.push((tmp_max, tmp_max_idx, tmp_max_idx));
+        return Ok(());
+    };
+
+    let rest = core::iter::once(x).chain(iter.map(|(_, x)| x));
+    compress_dual_from_iter(rest, start, sanitize, compressed);
+
+    if compressed.is_empty() {
+        return Err(MaxProdError::EmptyCompression);
+    }
+
+    Ok(())
+}
+
+/// The main loop of `compress_dual` starting at `first`, which must not be smaller than one.
+/// Also returns the trailing segment of numbers smaller than one, which `compress_dual`
+/// drops but the parallel version needs to stitch chunks back together.
+#[cfg(feature = "rayon")]
+pub(crate) fn compress_dual_from<T: Num + Copy + PartialOrd>(arr: &[T], first: usize, sanitize: impl Fn(T) -> T) -> (Vec<Segment<T>>, Option<Segment<T>>) {
+    let mut compressed = Vec::new();
+    let tail = compress_dual_from_iter(arr[first..].iter().copied(), first, sanitize, &mut compressed);
+    (compressed, tail)
+}
+
+/// `compress_dual_from` over the elements of an iterator, the first one has index `first`.
+/// The segments are appended to `compressed`, only the trailing small one is returned.
+fn compress_dual_from_iter<T: Num + Copy + PartialOrd>(iter: impl Iterator<Item = T>, first: usize, sanitize: impl Fn(T) -> T, compressed: &mut Vec<Segment<T>>) -> Option<Segment<T>> {
+    let mut tmp_prod = T::one();
+    let mut start = first;
+    let mut smaller = false; // true = compressing numbers smaller than one
+
+    // Ones don't change a product, but like the brute force, of two ranges with the same
+    // product the one with the smaller start and then the smaller end should win. So the
+    // ones at the end of a small segment become the start of the next large segment and
+    // the ones at the end of a large segment aren't part of its range.
+    let mut last_large = first;
+    let mut ones = None;
+    let mut last = first;
+
+    for (i, x) in (first..).zip(iter) {
+        let x = sanitize(x);
+        last = i;
+
+        if smaller {
+            if x < T::one() {
+                tmp_prod = tmp_prod * x;
+                ones = None;
+            } else if x == T::one() {
+                ones.get_or_insert(i);
+            } else {
+                let s = ones.take().unwrap_or(i);
+                compressed.push((tmp_prod, start, s - 1));
+                smaller = false;
+                tmp_prod = x;
+                start = s;
+                last_large = i;
+            }
+        } else {
+            if x > T::one() {
+                tmp_prod = tmp_prod * x;
+                last_large = i;
+            } else if x < T::one() {
+                compressed.push((tmp_prod, start, last_large));
+                smaller = true;
+                tmp_prod = x;
+                start = i;
+            }
+        }
+    }
+
+    if !smaller {
+        compressed.push((tmp_prod, start, last_large));
+        return None;
+    }
+
+    let end = ones.map_or(last, |s| s - 1);
+    Some((tmp_prod, start, end))
+}
+
+/// Fast algorithm for non-negative reals, `NaN` and `-inf` elements are treated like zeros.
+///
+/// A `+inf` element absorbs everything, so if there is one the result is the widest run
+/// of consecutive `+inf`s, the leftmost one if several are equally wide.
+///
+/// Products are multiplied in a different order than in the brute force, so if two ranges
+/// have products within rounding error of each other the returned range can differ from
+/// the one [`max_prod_brute_force`](crate::max_prod_brute_force) returns. With `f32` this
+/// already happens for values within about `1e-5` of one.
+///
+/// ```
+/// use max_prod::max_prod_fast_real;
+///
+/// // the zero splits the array, 3 * 0.9 * 4 beats 2
+/// assert_eq!(max_prod_fast_real([0.5, 2.0, 0.0, 3.0, 0.9, 4.0]), (3, 5));
+/// assert_eq!(max_prod_fast_real([0.5, f64::INFINITY, 2.0]), (1, 1));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real<T: Real + Copy>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    NonEmptySlice::try_from(arr.as_ref()).map_or((0, 0), max_prod_fast_real_non_empty)
+}
+
+/// [`max_prod_fast_real`] on a slice that is known not to be empty.
+///
+/// `max_prod_fast_real` checks for an empty slice and then calls this.
+///
+/// ```
+/// use max_prod::{max_prod_fast_real_non_empty, NonEmptySlice};
+///
+/// let arr = NonEmptySlice::try_from(&[0.5, 2.0, 3.0, 0.1][..]).unwrap();
+/// assert_eq!(max_prod_fast_real_non_empty(arr), (1, 2));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_non_empty<T: Real + Copy>(arr: NonEmptySlice<'_, T>) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_real_inner(arr).unwrap();
+    (i, j)
+}
+
+/// Like [`max_prod_fast_real`] but decides whether ones at either end of the range belong
+/// to it.
+///
+/// Ones don't change the product, `max_prod_fast_real` includes the ones right before the
+/// range and excludes those right after it. With `include_ones` the range is extended over
+/// the ones on both sides, without it they are trimmed from both ends, unless the range is
+/// a single element.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_opts;
+///
+/// assert_eq!(max_prod_fast_real_opts([1.0, 2.0, 1.0, 0.5], true), (0, 2));
+/// assert_eq!(max_prod_fast_real_opts([1.0, 2.0, 1.0, 0.5], false), (1, 1));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_opts<T: Real + Copy>(arr: impl AsRef<[T]>, include_ones: bool) -> (usize, usize) {
+    let arr = arr.as_ref();
+
+    if arr.is_empty() {
+        return (0, 0);
+    }
+
+    let (mut i, mut j) = max_prod_fast_real(arr);
+
+    if include_ones {
+        while i > 0 && arr[i - 1] == T::one() {
+            i -= 1;
+        }
+        while j + 1 < arr.len() && arr[j + 1] == T::one() {
+            j += 1;
+        }
+    } else {
+        while i < j && arr[i] == T::one() {
+            i += 1;
+        }
+        while i < j && arr[j] == T::one() {
+            j -= 1;
+        }
+    }
+
+    (i, j)
+}
+
+/// Like [`max_prod_fast_real`] but elements smaller than `epsilon` are treated as zeros.
+///
+/// `compress_dual` treats a value like `1e-300` as an ordinary element smaller than one, so
+/// ranges across it are still compared and its product can underflow. Below `epsilon` it
+/// acts as a hard separator instead, just like a zero, and no returned range contains it.
+/// An `epsilon` of zero gives exactly the result of `max_prod_fast_real`.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_epsilon;
+///
+/// assert_eq!(max_prod_fast_real_epsilon([3.0, 1e-300, 4.0, 5.0], 1e-100), (2, 3));
+/// assert_eq!(max_prod_fast_real_epsilon([1e300, 1e-250, 1e300], 0.0), (0, 2));
+/// assert_eq!(max_prod_fast_real_epsilon([1e300, 1e-250, 1e300], 1e-200), (0, 0));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_epsilon<T: Real + Copy>(arr: impl AsRef<[T]>, epsilon: T) -> (usize, usize) {
+    let arr = arr.as_ref();
+
+    if arr.is_empty() {
+        return (0, 0);
+    }
+
+    let (_, i, j) = max_prod_fast_real_iter(arr.iter().map(|&x| if x < epsilon { T::zero() } else { x })).unwrap();
+    (i, j)
+}
+
+/// Like [`max_prod_fast_real`] but compresses into `scratch` instead of allocating a new
+/// `Vec` of segments, to reuse one buffer for many arrays.
+///
+/// `scratch` is cleared first and holds whatever is left of the segments afterwards, its
+/// capacity is kept so once it has grown to fit the largest array nothing is allocated.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_inplace;
+///
+/// let mut scratch = Vec::new();
+/// assert_eq!(max_prod_fast_real_inplace([0.5, 2.0, 3.0, 0.1], &mut scratch), (1, 2));
+/// assert_eq!(max_prod_fast_real_inplace([2.0, 0.5, 4.0], &mut scratch), (0, 2));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_inplace<T: Real + Copy>(arr: impl AsRef<[T]>, scratch: &mut Vec<Segment<T>>) -> (usize, usize) {
+    let arr = arr.as_ref();
+
+    if arr.is_empty() {
+        scratch.clear();
+        return (0, 0);
+    }
+
+    let (_, i, j) = max_prod_fast_real_iter_with(arr.iter().copied(), scratch).unwrap();
+    (i, j)
+}
+
+/// Like [`max_prod_fast_real_with_value`] but returns a [`MaxProdResult`].
+///
+/// ```
+/// use max_prod::{max_prod_fast_real_result, MaxProdResult};
+///
+/// let res = max_prod_fast_real_result([0.5, 4.0, 0.5, 3.0]);
+/// assert_eq!(res, MaxProdResult { start: 1, end: 3, product: 6.0 });
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_result<T: Real + Copy>(arr: impl AsRef<[T]>) -> MaxProdResult<T> {
+    max_prod_fast_real_with_value(arr).into()
+}
+
+/// Like [`max_prod_fast_real`] but returns a [`Span`] to slice the array with.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_span;
+///
+/// let arr = [0.5, 4.0, 0.5, 3.0];
+/// assert_eq!(max_prod_fast_real_span(arr).slice(&arr), &[4.0, 0.5, 3.0]);
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_span<T: Real + Copy>(arr: impl AsRef<[T]>) -> Span {
+    max_prod_fast_real(arr).into()
+}
+
+/// Like [`max_prod_fast_real`] but also returns the product of the winning range.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_with_value;
+///
+/// assert_eq!(max_prod_fast_real_with_value([0.5, 4.0, 0.5, 3.0]), (6.0, 1, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_with_value<T: Real + Copy>(arr: impl AsRef<[T]>) -> (T, usize, usize) {
+    match NonEmptySlice::try_from(arr.as_ref()) {
+        Ok(arr) => max_prod_fast_real_inner(arr).unwrap(),
+        Err(_) => (T::zero(), 0, 0),
+    }
+}
+
+fn max_prod_fast_real_inner<T: Real + Copy>(arr: NonEmptySlice<T>) -> Result<(T, usize, usize), MaxProdError> {
+    max_prod_fast_real_iter(arr.iter().copied())
+}
+
+/// `max_prod_fast_real_inner` over the elements of an iterator, which is walked several
+/// times, for inputs that aren't a slice. The iterator must not be empty.
+pub(crate) fn max_prod_fast_real_iter<T: Real + Copy>(iter: impl Iterator<Item = T> + Clone) -> Result<(T, usize, usize), MaxProdError> {
+    // a new `Vec` only allocates once something is pushed
+    max_prod_fast_real_iter_with(iter, &mut Vec::new())
+}
+
+/// `max_prod_fast_real_iter` compressing into `scratch` instead of a new `Vec`.
+fn max_prod_fast_real_iter_with<T: Real + Copy>(iter: impl Iterator<Item = T> + Clone, scratch: &mut Vec<Segment<T>>) -> Result<(T, usize, usize), MaxProdError> {
+    scratch.clear();
+
+    if let Some((i, j)) = widest_inf_run_iter(iter.clone()) {
+        debug!("widest run of infinities [{i} .. {j}]");
+        return Ok((iter.clone().nth(i).unwrap(), i, j));
+    }
+
+    // without elements smaller than one the product only grows, so the best range starts
+    // at the front and ends at the last element larger than one
+    if iter.clone().all(|x| x >= T::one()) {
+        let end = iter.clone().enumerate().filter(|&(_, x)| x > T::one()).last().map_or(0, |(i, _)| i);
+        debug!("no element smaller than one, [0 .. {end}]");
+        return Ok((iter.take(end + 1).fold(T::one(), |p, x| p * x), 0, end));
+    }
+
+    compress_dual_iter_into(iter, nan_as_zero, scratch)?;
+    let (p, i, j) = combine_compressed_with(scratch, |_| {})?;
+    debug!("final [{i} .. {j}] = {}", p.to_f64().unwrap_or(f64::NAN));
+    Ok((p, i, j))
+}
+
+/// Like [`max_prod_fast_real_result`] but also returns how the segments were merged, to
+/// make the algorithm inspectable.
+///
+/// The segments of [`compress_dual`], without the small ones at either end, are merged
+/// from the back, every step takes the last
+/// three segments `c b a` and replaces them with `c b a` combined if that has a larger product
+/// than `c`, otherwise with `c` alone. The returned segments are those replacements in the
+/// order of the steps. If the result is found without merging, e.g. because there is no
+/// element smaller than one, no segments are returned.
+///
+/// ```
+/// use max_prod::{max_prod_fast_real_explain, MaxProdResult};
+///
+/// let (res, merged) = max_prod_fast_real_explain([2.0, 0.5, 3.0, 0.25, 4.0]);
+/// assert_eq!(res, MaxProdResult { start: 4, end: 4, product: 4.0 });
+/// assert_eq!(merged, vec![(3.0, 2, 2), (3.0, 0, 2)]);
+/// ```
+#[must_use]
+pub fn max_prod_fast_real_explain<T: Real + Copy>(arr: impl AsRef<[T]>) -> (MaxProdResult<T>, Vec<Segment<T>>) {
+    let arr = arr.as_ref();
+    let mut merged = Vec::new();
+
+    if arr.is_empty() || widest_inf_run(arr).is_some() || arr.iter().all(|&x| x >= T::one()) {
+        return (max_prod_fast_real_result(arr), merged);
+    }
+
+    let mut compressed = compress_dual_with(arr, nan_as_zero).unwrap();
+    let res = combine_compressed_with(&mut compressed, |s| merged.push(s)).unwrap();
+    (res.into(), merged)
+}
+
+/// Merges the segments from `compress_dual_with` from the back and keeps the best range seen.
+pub(crate) fn combine_compressed<T: Num + Copy + PartialOrd>(mut compressed: Vec<(T, usize, usize)>) -> Result<(T, usize, usize), MaxProdError> {
+    combine_compressed_with(&mut compressed, |_| {})
+}
+
+/// `combine_compressed` calling `on_merge` with the segment every merge step leaves behind.
+/// The segments are merged in place, afterwards at most two are left in `compressed`.
+fn combine_compressed_with<T: Num + Copy + PartialOrd>(compressed: &mut Vec<(T, usize, usize)>, mut on_merge: impl FnMut(Segment<T>)) -> Result<(T, usize, usize), MaxProdError> {
+    debug!("{} compressed segments", compressed.len());
+    let mut current_max = compressed[0];
+
+    while compressed.len() >= 3 {
+        let a = compressed.pop().unwrap(); // arr[n - 1]
+        let b = compressed.pop().unwrap(); // arr[n - 2]
+        let c = compressed.pop().unwrap(); // arr[n - 3]
+
+        trace!("a = [{} .. {}]  b = [{} .. {}]  c = [{} .. {}]", a.1, a.2, b.1, b.2, c.1, c.2);
+
+        let combined = (a.0 * b.0 * c.0, c.1, a.2);
+
+        let merged = if beats(&combined, &c) { combined } else { c };
+        compressed.push(merged);
+        on_merge(merged);
+
+        if beats(&combined, &current_max) {
+            current_max = combined;
+            trace!("new max (com) = [{} .. {}]", current_max.1, current_max.2);
+        }
+        if beats(&a, &current_max) {
+            current_max = a;
+            trace!("new max ( a ) = [{} .. {}]", current_max.1, current_max.2);
+        }
+        if beats(&c, &current_max) {
+            current_max = c;
+            trace!("new max ( c ) = [{} .. {}]", current_max.1, current_max.2);
+        }
+    }
+
+    check_range((current_max.1, current_max.2))?;
+    Ok(current_max)
+}
+
+/// Whether `x` has a larger product than `y`, or the same product and the smaller start
+/// and then the smaller end, the order of the brute force.
+fn beats<T: PartialOrd>(x: &Segment<T>, y: &Segment<T>) -> bool {
+    x.0 > y.0 || (x.0 == y.0 && (x.1, x.2) < (y.1, y.2))
+}
+
+pub(crate) fn widest_inf_run<T: Real + Copy>(arr: &[T]) -> Option<(usize, usize)> {
+    widest_inf_run_iter(arr.iter().copied())
+}
+
+fn widest_inf_run_iter<T: Real + Copy>(iter: impl Iterator<Item = T>) -> Option<(usize, usize)> {
+    let mut widest: Option<(usize, usize)> = None;
+    let mut start = None;
+
+    for (i, x) in iter.enumerate() {
+        if x > T::max_value() {
+            let s = *start.get_or_insert(i);
+
+            if widest.is_none_or(|(a, b)| i - s > b - a) {
+                widest = Some((s, i));
+            }
+        } else {
+            start = None;
+        }
+    }
+
+    widest
+}
+
+/// Mirror of `compress_dual`, the leading run of numbers larger than one is skipped
+/// and a trailing run of numbers larger than one is dropped.
+fn compress_dual_min<T: Real + Copy>(arr: &[T]) -> Result<Vec<(T, usize, usize)>, MaxProdError> {
+    let n = arr.len();
+    debug_assert!(n > 0, "compress_dual_min called with an empty slice");
+    let mut compressed = Vec::new();
+
+    let mut tmp_prod = T::one();
+    let mut start = 0;
+
+    let mut tmp_min = T::max_value();
+    let mut tmp_min_idx = 0;
+
+    while start < n && arr[start] > T::one()  {
+        if arr[start] < tmp_min {
+            tmp_min = arr[start];
+            tmp_min_idx = start;
+        }
+
+        start += 1;
+    }
+
+    if start == n {
+        return Ok(vec![(tmp_min, tmp_min_idx, tmp_min_idx)]);
+    }
+
+    let mut larger = false; // true = compressing numbers larger than one
+    let first = start;
+
+    // ones are handled like in `compress_dual`
+    let mut last_small = first;
+    let mut ones = None;
+
+    for (i, &x) in arr.iter().enumerate().skip(first) {
+        if larger {
+            if x > T::one() {
+                tmp_prod = tmp_prod * x;
+                ones = None;
+            } else if x == T::one() {
+                ones.get_or_insert(i);
+            } else {
+                let s = ones.take().unwrap_or(i);
+                compressed.push((tmp_prod, start, s - 1));
+                larger = false;
+                tmp_prod = x;
+                start = s;
+                last_small = i;
+            }
+        } else {
+            if x < T::one() {
+                tmp_prod = tmp_prod * x;
+                last_small = i;
+            } else if x > T::one() {
+                compressed.push((tmp_prod, start, last_small));
+                larger = true;
+                tmp_prod = x;
+                start = i;
+            }
+        }
+    }
+
+    if !larger {
+        compressed.push((tmp_prod, start, last_small));
+    }
+
+    if compressed.is_empty() {
+        return Err(MaxProdError::EmptyCompression);
+    }
+
+    Ok(compressed)
+}
+
+/// Finds the range with the smallest product, the input must be non-negative.
+///
+/// ```
+/// use max_prod::min_prod_fast_real;
+///
+/// assert_eq!(min_prod_fast_real([2.0, 0.5, 0.25, 3.0]), (1, 2));
+/// ```
+#[must_use]
+pub fn min_prod_fast_real<T: Real + Copy>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let arr = arr.as_ref();
+
+    if arr.is_empty() {
+        return (0, 0);
+    }
+
+    let mut compressed = compress_dual_min(arr).unwrap();
+    let mut current_min = compressed[0];
+
+    while compressed.len() >= 3 {
+        let a = compressed.pop().unwrap(); // arr[n - 1]
+        let b = compressed.pop().unwrap(); // arr[n - 2]
+        let c = compressed.pop().unwrap(); // arr[n - 3]
+
+        let combined = (a.0 * b.0 * c.0, c.1, a.2);
+
+        if combined.0 < c.0 {
+            compressed.push(combined);
+        } else {
+            compressed.push(c);
+        }
+
+        if combined.0 < current_min.0 {
+            current_min = combined;
+        }
+        if a.0 < current_min.0 {
+            current_min = a;
+        }
+        if c.0 < current_min.0 {
+            current_min = c;
+        }
+    }
+
+    (current_min.1, current_min.2)
+}
+
+/// Like [`max_prod_fast_real`] but returns the winning subarray itself, empty if `arr` is empty.
+///
+/// ```
+/// use max_prod::max_prod_subslice_real;
+///
+/// let v = vec![0.5, 4.0, 0.5, 3.0, 0.1];
+/// assert_eq!(max_prod_subslice_real(&v), &[4.0, 0.5, 3.0]);
+/// ```
+#[must_use]
+pub fn max_prod_subslice_real<T: Real + Copy>(arr: &[T]) -> &[T] {
+    if arr.is_empty() {
+        return arr;
+    }
+
+    let (i, j) = max_prod_fast_real(arr);
+    &arr[i..=j]
+}
+
+/// Like [`max_prod_subslice_real`] but returns an iterator over the winning elements, empty
+/// if `arr` is empty.
+///
+/// ```
+/// use max_prod::max_prod_elements_real;
+///
+/// let v = vec![0.5, 4.0, 0.5, 3.0, 0.1];
+/// assert_eq!(max_prod_elements_real(&v).sum::<f64>(), 7.5);
+/// ```
+#[must_use = "the elements are only returned, `arr` is left as it is"]
+pub fn max_prod_elements_real<T: Real + Copy>(arr: &[T]) -> impl Iterator<Item = &T> {
+    max_prod_subslice_real(arr).iter()
+}
+
+/// Kadane-style algorithm for reals, negative values are allowed.
+///
+/// Tracks the largest and the smallest product ending at every index, a negative value
+/// swaps their roles, so runs of alternating signs are handled correctly. Zeros reset
+/// both products. Like the brute force, if no range has a positive product `(0, 0)` is
+/// returned.
+///
+/// ```
+/// use max_prod::max_prod_kadane_real;
+///
+/// // the two negative numbers cancel out
+/// assert_eq!(max_prod_kadane_real([-2.0, 0.5, -3.0, 0.0, 2.0]), (0, 2));
+/// ```
+#[must_use]
+pub fn max_prod_kadane_real<T: Real + Copy>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let (_, i, j) = max_prod_kadane_with_value(arr.as_ref()).unwrap();
+    (i, j)
+}
+
+/// The ranges with the maximum and the minimum product in a single pass, negative values
+/// are allowed.
+///
+/// Like [`max_prod_kadane_real`] this tracks the largest and the smallest product ending at
+/// every index. The maximum is the same range `max_prod_kadane_real` returns, for the
+/// minimum of two ranges with the same product the one with the smaller start and then the
+/// smaller end wins, like in a brute force. Ranges containing a zero have a product of
+/// zero, so the minimum is never larger than zero if there is one.
+///
+/// ```
+/// use max_prod::max_min_prod_real;
+///
+/// assert_eq!(max_min_prod_real([2.0, -3.0, 4.0, 0.5]), ((2, 2), (0, 2)));
+/// ```
+#[must_use]
+pub fn max_min_prod_real<T: Real + Copy>(arr: impl AsRef<[T]>) -> ((usize, usize), (usize, usize)) {
+    let arr = arr.as_ref();
+
+    if arr.is_empty() {
+        return ((0, 0), (0, 0));
+    }
+
+    let mut best = None;
+    let mut min_prod = arr[0];
+    let mut min = (0, 0);
+
+    // largest and smallest product of a range ending at the current index,
+    // together with the start of that range; None right after a zero
+    let mut current: Option<((T, usize), (T, usize))> = None;
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x == T::zero() {
+            current = None;
+
+            // (0, i) is the first range containing this zero
+            if T::zero() < min_prod {
+                min_prod = T::zero();
+                min = (0, i);
+            }
+            continue;
+        }
+
+        let (hi, lo) = match current {
+            None => ((x, i), (x, i)),
+            Some((hi, lo)) => {
+                let mut new_hi = (x, i);
+                let mut new_lo = (x, i);
+
+                for (p, start) in [(hi.0 * x, hi.1), (lo.0 * x, lo.1)] {
+                    if p > new_hi.0 || (p == new_hi.0 && start < new_hi.1) {
+                        new_hi = (p, start);
+                    }
+                    if p < new_lo.0 || (p == new_lo.0 && start < new_lo.1) {
+                        new_lo = (p, start);
+                    }
+                }
+
+                (new_hi, new_lo)
+            }
+        };
+        current = Some((hi, lo));
+
+        // not consider, a later range can start before the best one and then wins a tie
+        if hi.0 > T::zero() && best.is_none_or(|(p, start, _)| hi.0 > p || (hi.0 == p && hi.1 < start)) {
+            best = Some((hi.0, hi.1, i));
+        }
+        if lo.0 < min_prod || (lo.0 == min_prod && (lo.1, i) < min) {
+            min_prod = lo.0;
+            min = (lo.1, i);
+        }
+    }
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    ((i, j), min)
+}
+
+/// Fails with [`MaxProdError::Empty`] for an empty slice instead of returning `(0, 0)`.
+///
+/// ```
+/// use max_prod::{try_max_prod_fast_real, MaxProdError};
+///
+/// assert_eq!(try_max_prod_fast_real([0.5, 2.0, 3.0]), Ok((1, 2)));
+/// assert_eq!(try_max_prod_fast_real::<f64>([]), Err(MaxProdError::Empty));
+/// ```
+pub fn try_max_prod_fast_real<T: Real + Copy>(arr: impl AsRef<[T]>) -> Result<(usize, usize), MaxProdError> {
+    let (_, i, j) = max_prod_fast_real_inner(NonEmptySlice::try_from(arr.as_ref())?)?;
+    Ok((i, j))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use num::traits::Num;
+
+    use crate::divide_conquer::max_prod_divide_conquer_real;
+    use crate::max_prod::{max_prod_brute_force, max_prod_brute_force_improved, prod};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_real_basic() {
+        let arr = vec![0.1, 0.5, 13.0, 2.0, 0.1, 4.0, 6.0, 7.0, 8.0, 0.1, 0.2];
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+    }
+
+    #[test]
+    fn test_real_leading_small() {
+        let arr = vec![0.9, 0.8, 1.5];
+        assert_eq!(max_prod_fast_real(&arr), (2, 2));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+
+        let arr = vec![0.9, 0.1];
+        assert_eq!(max_prod_fast_real(&arr), (0, 0));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+
+        let arr = vec![0.1, 0.8, 0.9, 0.3];
+        assert_eq!(max_prod_fast_real_with_value(&arr), (0.9, 2, 2));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+    }
+
+    #[test]
+    fn test_real_large_runs_dominated() {
+        // every run of numbers larger than one is too small to make up for its neighbours,
+        // so the largest single element wins
+        let arr = vec![0.9, 0.9, 1.05, 0.9];
+        assert_eq!(max_prod_fast_real_with_value(&arr), (1.05, 2, 2));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+
+        let arr = vec![0.5, 1.05, 0.5, 1.1, 0.9, 1.02];
+        assert_eq!(max_prod_fast_real(&arr), (3, 3));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+    }
+
+    #[test]
+    fn test_random_real_near_one() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..12);
+            let a: Vec<f64> = Uniform::new(0.85, 1.1).sample_iter(&mut rng).take(n).collect();
+            assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a), "{a:?}");
+        }
+    }
+
+    #[test]
+    fn test_real_exact_ties() {
+        assert_eq!(max_prod_fast_real([1.0, 0.0, 2.0, 0.0, 2.0]), (2, 2));
+        assert_eq!(max_prod_fast_real([1.5, 0.5, 2.0, 0.0, 2.0]), (2, 2));
+        assert_eq!(max_prod_fast_real([1.5, 0.5, 2.0, 0.5, 2.0]), (2, 2));
+    }
+
+    #[test]
+    fn test_exhaustive_real_ties() {
+        // powers of two multiply exactly, so equal products really are equal
+        let values = [0.0, 0.25, 0.5, 1.0, 2.0, 4.0];
+
+        for n in 1..=6 {
+            for k in 0..values.len().pow(n as u32) {
+                let a: Vec<f64> = (0..n).map(|d| values[k / values.len().pow(d as u32) % values.len()]).collect();
+                assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a), "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_real_mostly_small() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..10);
+            let a: Vec<f64> = Uniform::new(0.0, 1.1).sample_iter(&mut rng).take(n).collect();
+            assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a));
+        }
+    }
+
+    #[test]
+    fn test_real_single_element() {
+        // [0.5] takes the early return for a leading run of small numbers, [2.0] and [1.0]
+        // take the fast path for arrays without elements smaller than one
+        for x in [0.5, 2.0, 1.0, 0.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(max_prod_fast_real([x]), (0, 0), "{x}");
+            assert_eq!(min_prod_fast_real([x]), (0, 0), "{x}");
+        }
+
+        assert_eq!(max_prod_fast_real_with_value([0.5]), (0.5, 0, 0));
+        assert_eq!(max_prod_fast_real_with_value([2.0]), (2.0, 0, 0));
+        assert_eq!(max_prod_fast_real_with_value([1.0f32]), (1.0, 0, 0));
+    }
+
+    #[test]
+    fn test_elements() {
+        let a = [0.5, 2.0, 0.25, 3.0, 4.0];
+        assert!(max_prod_elements_real(&a).eq(&[3.0, 4.0]));
+        assert_eq!(max_prod_elements_real::<f64>(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_real_all_large() {
+        let arr = vec![1.1, 2.0, 3.0, 1.5];
+        assert_eq!(max_prod_fast_real(&arr), (0, 3));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+
+        let arr = vec![1.0, 2.0, 1.0, 3.0, 1.0];
+        assert_eq!(max_prod_fast_real_with_value(&arr), (6.0, 0, 3));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+
+        let arr = vec![1.0, 1.0];
+        assert_eq!(max_prod_fast_real(&arr), (0, 0));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+    }
+
+    #[test]
+    fn test_random_real_all_large() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(1..30);
+            let a: Vec<f64> = Uniform::new(1.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            assert_eq!(max_prod_fast_real(&a), (0, n - 1));
+            assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a));
+        }
+    }
+
+    #[test]
+    fn test_compress_dual_segments() {
+        assert_eq!(compress_dual::<f64>(&[]), Err(MaxProdError::Empty));
+        assert_eq!(compress_dual(&[0.5, 0.25, 0.75]), Ok(vec![(0.09375, 0, 2)]));
+        assert_eq!(compress_dual(&[1.0, 1.0]), Ok(vec![(1.0, 0, 1)]));
+        assert_eq!(compress_dual(&[0.5, 1.0, 0.5, 2.0, 1.0]), Ok(vec![(0.5, 0, 0), (1.0, 1, 1), (0.5, 2, 2), (2.0, 3, 4)]));
+        assert_eq!(compress_dual(&[2.0, 0.5, 1.0, 0.5, 1.0]), Ok(vec![(2.0, 0, 0), (0.25, 1, 4)]));
+        assert_eq!(compress_dual(&[2.0, 1.0, 0.5, 1.0]), Ok(vec![(2.0, 0, 1), (0.5, 2, 3)]));
+        assert_eq!(compress_dual(&[0.5, 1.0, 2.0]), Ok(vec![(0.5, 0, 0), (2.0, 1, 2)]));
+
+        assert_eq!(compress_dual_with::<f64>(&[], |x| x), Err(MaxProdError::Empty));
+        assert_eq!(compress_dual_with(&[0.5, 0.25, 0.75], |x| x), Ok(vec![(0.75, 2, 2)]));
+        assert_eq!(compress_dual_with(&[1.0, 1.0], |x| x), Ok(vec![(1.0, 0, 0)]));
+        assert_eq!(compress_dual_with(&[0.5, f64::NAN, 2.0], nan_as_zero), Ok(vec![(2.0, 2, 2)]));
+    }
+
+    #[test]
+    fn test_compress_dual_negative() {
+        assert_eq!(compress_dual(&[-0.5, 2.0]), Err(MaxProdError::Negative { index: 0 }));
+        assert_eq!(compress_dual(&[0.5, 2.0, -3.0]), Err(MaxProdError::Negative { index: 2 }));
+        assert_eq!(compress_dual(&[f64::NEG_INFINITY, 2.0]), Err(MaxProdError::Negative { index: 0 }));
+        assert_eq!(MaxProdError::Negative { index: 2 }.to_string(), "negative element at index 2");
+
+        // -inf and NaN become zeros first
+        assert_eq!(compress_dual_with(&[f64::NEG_INFINITY, 2.0], nan_as_zero), Ok(vec![(2.0, 1, 1)]));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "negative element")]
+    fn test_compress_dual_negative_debug() {
+        let _ = compress_dual_with(&[-0.5, 2.0], |x| x);
+    }
+
+    #[test]
+    fn test_random_compress_dual_cumulative() {
+        let mut rng = test_rng();
+        for _ in 0..300 {
+            let n = rng.gen_range(1..30);
+            let a: Vec<f64> = Uniform::new(0.1, 3.0).sample_iter(&mut rng).take(n).collect();
+
+            let segments = compress_dual_with_cumulative(&a).unwrap();
+            let plain = compress_dual(&a).unwrap();
+            assert_eq!(segments.len(), plain.len());
+
+            let mut previous = 1.0;
+            for (&(p, i, j, cumulative), &segment) in segments.iter().zip(&plain) {
+                assert_eq!((p, i, j), segment);
+                assert_eq!(cumulative, previous * p);
+                assert!((cumulative / prod(&a, 0, j) - 1.0).abs() < 1e-9, "{a:?}");
+                previous = cumulative;
+            }
+
+            assert!((previous / prod(&a, 0, a.len() - 1) - 1.0).abs() < 1e-9, "{a:?}");
+        }
+
+        assert_eq!(compress_dual_with_cumulative(&[0.5, 0.25]), Ok(vec![(0.125, 0, 1, 0.125)]));
+        assert_eq!(compress_dual_with_cumulative(&[0.25, 2.0]), Ok(vec![(0.25, 0, 0, 0.25), (2.0, 1, 1, 0.5)]));
+        assert_eq!(compress_dual_with_cumulative::<f64>(&[]), Err(MaxProdError::Empty));
+    }
+
+    #[test]
+    fn test_random_compress_dual() {
+        let mut rng = test_rng();
+        // powers of two multiply exactly, so the products can be compared exactly
+        let values = [0.0, 0.25, 0.5, 1.0, 2.0, 4.0];
+
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..20);
+            let a: Vec<f64> = (0..n).map(|_| values[rng.gen_range(0..values.len())]).collect();
+            let segments = compress_dual(&a).unwrap();
+
+            // every index is covered exactly once, in order
+            assert_eq!(segments[0].1, 0, "{a:?}");
+            assert_eq!(segments[segments.len() - 1].2, n - 1, "{a:?}");
+            for w in segments.windows(2) {
+                assert_eq!(w[1].1, w[0].2 + 1, "{a:?}");
+            }
+
+            for (k, &(p, i, j)) in segments.iter().enumerate() {
+                assert!(i <= j, "{a:?}");
+                assert_eq!(p, prod(&a, i, j), "{a:?}");
+
+                let small = a[i] < 1.0;
+                if small {
+                    assert!(a[i..=j].iter().all(|&x| x <= 1.0) && p < 1.0, "{a:?}");
+                } else {
+                    assert!(a[i..=j].iter().all(|&x| x >= 1.0) && p >= 1.0, "{a:?}");
+                }
+
+                if k > 0 {
+                    assert_ne!(small, a[segments[k - 1].1] < 1.0, "{a:?}");
+                }
+            }
+
+            // the segments max_prod_fast_real merges are the large ones and those in between
+            let inner = compress_dual_with(&a, |x| x).unwrap();
+            if a.iter().any(|&x| x >= 1.0) {
+                for &(p, i, j) in &inner {
+                    assert!(segments.iter().any(|&(q, k, l)| p == q && k <= i && j <= l), "{a:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        // 3 * 0.5 * 4 beats 3, so [2 .. 4] is merged first and then extended by the leading 2
+        let arr = [2.0, 0.25, 3.0, 0.5, 4.0];
+        let (res, merged) = max_prod_fast_real_explain(arr);
+        assert_eq!(compress_dual(&arr).unwrap().len(), 5);
+        assert_eq!(merged, vec![(6.0, 2, 4), (3.0, 0, 4)]);
+        assert_eq!((res.product, res.start, res.end), max_prod_fast_real_with_value(arr));
+        assert_eq!((res.start, res.end), (2, 4));
+
+        let (res, merged) = max_prod_fast_real_explain([1.0, 2.0, 3.0]);
+        assert_eq!((res.start, res.end), (0, 2));
+        assert!(merged.is_empty());
+
+        let (res, merged) = max_prod_fast_real_explain::<f64>([]);
+        assert_eq!(res, MaxProdResult { start: 0, end: 0, product: 0.0 });
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    fn test_random_explain() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(30).collect();
+            let (res, merged) = max_prod_fast_real_explain(&a);
+            assert_eq!((res.product, res.start, res.end), max_prod_fast_real_with_value(&a));
+
+            let segments = compress_dual_with(&a, nan_as_zero).unwrap();
+            assert_eq!(merged.len(), segments.len() / 2);
+        }
+    }
+
+    #[test]
+    fn test_real_ones() {
+        let arr = vec![0.5, 1.0, 1.0, 2.0, 0.5];
+        assert_eq!(max_prod_fast_real(&arr), (1, 3));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+
+        let arr = vec![2.0, 1.0, 1.0, 0.5, 1.0, 3.0, 1.0];
+        assert_eq!(max_prod_fast_real(&arr), (0, 5));
+        assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr));
+
+        for arr in [vec![1.0, 0.5], vec![0.5, 1.0], vec![0.5, 1.0, 1.0, 0.5], vec![1.0, 0.5, 2.0, 1.0]] {
+            assert_eq!(max_prod_fast_real(&arr), max_prod_brute_force_improved(&arr), "arr = {:?}", arr);
+            assert_eq!(min_prod_fast_real(&arr), min_prod_brute_force(&arr), "arr = {:?}", arr);
+        }
+    }
+
+    #[test]
+    fn test_opts_ones() {
+        assert_eq!(max_prod_fast_real([2.0, 1.0, 1.0]), (0, 0));
+        assert_eq!(max_prod_fast_real_opts([2.0, 1.0, 1.0], true), (0, 2));
+        assert_eq!(max_prod_fast_real_opts([2.0, 1.0, 1.0], false), (0, 0));
+
+        assert_eq!(max_prod_fast_real_opts([0.5, 1.0, 2.0, 0.5, 3.0, 1.0, 0.1], true), (1, 5));
+        assert_eq!(max_prod_fast_real_opts([0.5, 1.0, 2.0, 0.5, 3.0, 1.0, 0.1], false), (2, 4));
+
+        // ones inside the range stay
+        assert_eq!(max_prod_fast_real_opts([2.0, 1.0, 3.0], false), (0, 2));
+        assert_eq!(max_prod_fast_real_opts([1.0, 1.0], true), (0, 1));
+        assert_eq!(max_prod_fast_real_opts([1.0, 1.0], false), (0, 0));
+        assert_eq!(max_prod_fast_real_opts::<f64>([], true), (0, 0));
+    }
+
+    #[test]
+    fn test_random_inplace() {
+        let mut rng = test_rng();
+        let mut scratch = Vec::new();
+
+        for _ in 0..2000 {
+            let n = rng.gen_range(0..40);
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+
+            let capacity = scratch.capacity();
+            assert_eq!(max_prod_fast_real_inplace(&a, &mut scratch), max_prod_fast_real(&a), "a = {:?}", a);
+            assert!(scratch.capacity() >= capacity);
+        }
+
+        // the buffer grew to fit the largest array and was reused after that
+        assert!(scratch.capacity() > 0);
+    }
+
+    #[test]
+    fn test_inplace_stale_scratch() {
+        let mut scratch = vec![(9.0, 7, 8); 5];
+        assert_eq!(max_prod_fast_real_inplace([0.5, 2.0, 0.5, 3.0], &mut scratch), (1, 3));
+        assert_eq!(max_prod_fast_real_inplace([3.0, 2.0], &mut scratch), (0, 1));
+        assert!(scratch.is_empty());
+
+        scratch.push((9.0, 7, 8));
+        assert_eq!(max_prod_fast_real_inplace::<f64>([], &mut scratch), (0, 0));
+        assert!(scratch.is_empty());
+    }
+
+    #[test]
+    fn test_epsilon() {
+        let arr = [3.0, 1e-300, 4.0, 5.0];
+        assert_eq!(max_prod_fast_real_epsilon(arr, 1e-100), (2, 3));
+        assert_eq!(max_prod_fast_real_epsilon(arr, 0.0), max_prod_fast_real(arr));
+
+        let segments = compress_dual_with(&arr, |x| if x < 1e-100 { 0.0 } else { x }).unwrap();
+        assert_eq!(segments, vec![(3.0, 0, 0), (0.0, 1, 1), (20.0, 2, 3)]);
+
+        // the product across the tiny element is larger than either side alone
+        let arr = [1e300, 1e-250, 1e300];
+        assert_eq!(max_prod_fast_real_epsilon(arr, 0.0), (0, 2));
+        assert_eq!(max_prod_fast_real_epsilon(arr, 1e-250), (0, 2));
+        assert_eq!(max_prod_fast_real_epsilon(arr, 1e-200), (0, 0));
+
+        assert_eq!(max_prod_fast_real_epsilon([0.5, 1e-20, 0.25], 1e-10), (0, 0));
+        assert_eq!(max_prod_fast_real_epsilon([1e-20, 1e-30], 1e-10), (0, 0));
+        assert_eq!(max_prod_fast_real_epsilon::<f64>([], 1e-10), (0, 0));
+    }
+
+    #[test]
+    fn test_random_epsilon() {
+        let mut rng = test_rng();
+
+        for _ in 0..2000 {
+            let n = rng.gen_range(1..12);
+            let mut a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            for _ in 0..rng.gen_range(0..3) {
+                a[rng.gen_range(0..n)] = 1e-200;
+            }
+            let zeroed: Vec<f64> = a.iter().map(|&x| if x < 1e-100 { 0.0 } else { x }).collect();
+
+            assert_eq!(max_prod_fast_real_epsilon(&a, 1e-100), max_prod_fast_real(&zeroed), "a = {:?}", a);
+            assert_eq!(max_prod_fast_real_epsilon(&a, 0.0), max_prod_fast_real(&a), "a = {:?}", a);
+        }
+    }
+
+    #[test]
+    fn test_random_real_ones() {
+        let mut rng = test_rng();
+
+        for _ in 0..2000 {
+            let n = rng.gen_range(1..12);
+            // random reals never tie, so all ties come from the ones
+            let mut a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            for x in a.iter_mut() {
+                if rng.gen_bool(0.4) {
+                    *x = 1.0;
+                }
+            }
+
+            assert_eq!(max_prod_fast_real(&a), max_prod_brute_force_improved(&a), "a = {:?}", a);
+            assert_eq!(min_prod_fast_real(&a), min_prod_brute_force(&a), "a = {:?}", a);
+        }
+    }
+
+    /// Both ranges have the same product up to a relative error of `eps`, the products
+    /// are computed in `f64` so the check itself doesn't add much rounding.
+    fn assert_prod_close<T: Copy + Into<f64>>(arr: &[T], a: (usize, usize), b: (usize, usize), eps: f64) {
+        let arr: Vec<f64> = arr.iter().map(|&x| x.into()).collect();
+        let (pa, pb) = (prod(&arr, a.0, a.1), prod(&arr, b.0, b.1));
+        assert!((pa - pb).abs() <= eps * pa.max(pb), "{:?} = {} != {:?} = {}, arr = {:?}", a, pa, b, pb, arr);
+    }
+
+    /// The ranges must be the same, unless their products are within a relative `eps` of
+    /// each other, two ranges that close can legitimately be ordered differently after
+    /// rounding. Such near ties are printed.
+    fn assert_same_range<T: Copy + Into<f64>>(arr: &[T], got: (usize, usize), expected: (usize, usize), eps: f64) {
+        if got == expected {
+            return;
+        }
+
+        let arr: Vec<f64> = arr.iter().map(|&x| x.into()).collect();
+        let (pg, pe) = (prod(&arr, got.0, got.1), prod(&arr, expected.0, expected.1));
+        assert!((pg - pe).abs() <= eps * pg.max(pe), "{:?} = {} != {:?} = {}, arr = {:?}", got, pg, expected, pe, arr);
+
+        println!("near tie: {:?} = {} and {:?} = {}", got, pg, expected, pe);
+    }
+
+    #[test]
+    fn test_real_f32() {
+        let arr = [0.5f32, 4.0, 0.5, 3.0, 0.1];
+        assert_eq!(max_prod_fast_real_with_value(arr), (6.0, 1, 3));
+
+        let arr = [0.5f32, 1.0, 1.0, 2.0, 0.5];
+        assert_eq!(max_prod_fast_real(arr), max_prod_brute_force_improved(arr));
+
+        let arr = [0.9f32, f32::NAN, 0.8, f32::INFINITY, 0.1];
+        assert_eq!(max_prod_fast_real(arr), (3, 3));
+
+        // the neighbours of one, their products round back to one or to its neighbours
+        let up = 1.0 + f32::EPSILON;
+        let down = 1.0 - f32::EPSILON / 2.0;
+        let arr = [down, up, up, down, up];
+        assert_eq!(max_prod_fast_real(arr), max_prod_brute_force_improved(arr));
+    }
+
+    #[test]
+    fn test_random_real_f32() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let a: Vec<f32> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(100).collect();
+            assert_prod_close(&a, max_prod_fast_real(&a), max_prod_brute_force_improved(&a), 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_random_real_f32_near_one() {
+        let mut rng = test_rng();
+        for _ in 0..2000 {
+            let n = rng.gen_range(1..40);
+            // close to one the rounding of the products decides which range wins
+            let a: Vec<f32> = Uniform::new(0.99999, 1.00001).sample_iter(&mut rng).take(n).collect();
+            assert_prod_close(&a, max_prod_fast_real(&a), max_prod_brute_force_improved(&a), 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_real_nan() {
+        let arr = vec![0.5, f64::NAN, 3.0, 4.0];
+        assert_eq!(max_prod_fast_real(&arr), (2, 3));
+
+        let arr = vec![3.0, 4.0, f64::NAN, 0.5, 5.0];
+        assert_eq!(max_prod_fast_real(&arr), (0, 1));
+
+        let arr = vec![f64::NAN, 0.5, f64::NAN];
+        assert_eq!(max_prod_fast_real(&arr), (1, 1));
+
+        let arr = vec![f64::NAN];
+        assert_eq!(max_prod_fast_real(&arr), (0, 0));
+    }
+
+    #[test]
+    fn test_real_inf() {
+        let arr = vec![2.0, f64::INFINITY, 0.5];
+        assert_eq!(max_prod_fast_real_with_value(&arr), (f64::INFINITY, 1, 1));
+
+        let arr = vec![f64::INFINITY, 3.0, f64::INFINITY, f64::INFINITY, 0.0, f64::INFINITY];
+        assert_eq!(max_prod_fast_real(&arr), (2, 3));
+
+        let arr = vec![f64::INFINITY; 4];
+        assert_eq!(max_prod_fast_real(&arr), (0, 3));
+
+        let arr = vec![f64::INFINITY];
+        assert_eq!(max_prod_fast_real(&arr), (0, 0));
+    }
+
+    #[test]
+    fn test_real_neg_inf() {
+        let arr = vec![2.0, 3.0, f64::NEG_INFINITY, 4.0];
+        assert_eq!(max_prod_fast_real(&arr), (0, 1));
+
+        let arr = vec![f64::NEG_INFINITY, 0.5];
+        assert_eq!(max_prod_fast_real(&arr), (1, 1));
+    }
+
+    #[test]
+    fn test_same_range_near_tie() {
+        // the first product is exactly 3 without rounding
+        let a = [3.0, 0.7, 1.0 / 0.7];
+        assert_ne!(prod(a, 0, 2), 3.0);
+        assert_same_range(&a, (0, 2), (0, 0), 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_same_range_divergence() {
+        let a = [2.0, 0.4, 3.0];
+        assert_same_range(&a, (0, 2), (2, 2), 1e-12);
+    }
+
+    #[test]
+    fn test_random_real() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(100).collect();
+            let expected = max_prod_brute_force_improved(&a);
+            assert_same_range(&a, max_prod_fast_real(&a), expected, 1e-12);
+            assert_same_range(&a, max_prod_divide_conquer_real(&a), expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_random_real2() {
+        let mut rng = test_rng();
+        for i in 1..200 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(i / 2).collect();
+            println!("a = {:?}", a);
+            let expected = max_prod_brute_force_improved(&a);
+            assert_same_range(&a, max_prod_fast_real(&a), expected, 1e-12);
+            assert_same_range(&a, max_prod_divide_conquer_real(&a), expected, 1e-12);
+        }
+    }
+
+    fn min_prod_brute_force<T: Num + Copy + PartialOrd>(arr: &[T]) -> (usize, usize) {
+        let mut min_prod = arr[0];
+        let mut min = (0, 0);
+        let n = arr.len();
+
+        for i in 0..n {
+            let mut prod = T::one();
+            for (j, &x) in arr.iter().enumerate().skip(i) {
+                prod = prod * x;
+
+                if prod < min_prod {
+                    min_prod = prod;
+                    min = (i, j);
+                }
+            }
+        }
+
+        min
+    }
+
+    #[test]
+    fn test_max_min_basic() {
+        let arr = vec![2.0, -3.0, 0.0, -2.0, -4.0];
+        assert_eq!(max_min_prod_real(&arr), ((3, 4), (0, 1)));
+
+        let arr = vec![0.5, 0.0, 3.0];
+        assert_eq!(max_min_prod_real(&arr), ((2, 2), (0, 1)));
+
+        assert_eq!(max_min_prod_real([-1.0]), ((0, 0), (0, 0)));
+        assert_eq!(max_min_prod_real::<f64>([]), ((0, 0), (0, 0)));
+    }
+
+    #[test]
+    fn test_random_max_min() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..20);
+            let mut a: Vec<f64> = Uniform::new(-2.0, 2.0).sample_iter(&mut rng).take(n).collect();
+            if rng.gen_bool(0.3) {
+                a[rng.gen_range(0..n)] = 0.0;
+            }
+
+            let (max, min) = max_min_prod_real(&a);
+            assert_eq!(max, max_prod_brute_force(&a), "{a:?}");
+            assert_eq!(min, min_prod_brute_force(&a), "{a:?}");
+        }
+    }
+
+    #[test]
+    fn test_min_real_basic() {
+        let arr = vec![3.0, 0.5, 0.2, 4.0, 0.1, 1.5, 2.0, 0.9];
+        assert_eq!(min_prod_fast_real(&arr), (1, 4));
+        assert_eq!(min_prod_fast_real(&arr), min_prod_brute_force(&arr));
+
+        let arr = vec![3.0, 2.0, 4.0];
+        assert_eq!(min_prod_fast_real(&arr), (1, 1));
+        assert_eq!(min_prod_fast_real(&arr), min_prod_brute_force(&arr));
+    }
+
+    #[test]
+    fn test_random_min_real() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(100).collect();
+            assert_eq!(min_prod_fast_real(&a), min_prod_brute_force(&a));
+        }
+
+        for i in 1..200 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(i / 2 + 1).collect();
+            assert_eq!(min_prod_fast_real(&a), min_prod_brute_force(&a));
+        }
+    }
+
+    #[test]
+    fn test_random_kadane_real() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let a: Vec<f64> = Uniform::new_inclusive(-2.0, 2.0).sample_iter(&mut rng).take(50).collect();
+            assert_eq!(max_prod_kadane_real(&a), max_prod_brute_force_improved(&a));
+        }
+    }
+
+    #[test]
+    fn test_kadane_real_basic() {
+        let a = vec![-2.0, -3.0, -4.0, 0.5, -1.0];
+        assert_eq!(max_prod_kadane_real(&a), (0, 4));
+        assert_eq!(max_prod_kadane_real(&a), max_prod_brute_force(&a));
+
+        let a = vec![-2.0, 0.5, -3.0, 0.5, -4.0];
+        assert_eq!(max_prod_kadane_real(&a), (2, 4));
+        assert_eq!(max_prod_kadane_real(&a), max_prod_brute_force(&a));
+
+        let a = vec![0.5, 0.0, -3.0, -0.5, 0.0, 1.2];
+        assert_eq!(max_prod_kadane_real(&a), (2, 3));
+
+        let a = vec![-0.5, 0.5];
+        assert_eq!(max_prod_kadane_real(&a), (1, 1));
+
+        let a = vec![-0.5];
+        assert_eq!(max_prod_kadane_real(&a), (0, 0));
+
+        let a = vec![1.0, 0.5, 1.0, -0.5, 3.0, 5.0, 0.5, 1.8];
+        assert_eq!(max_prod_kadane_real(&a), max_prod_brute_force(&a));
+    }
+
+    #[test]
+    fn test_with_value_real() {
+        let arr = vec![0.1, 0.5, 13.0, 2.0, 0.1, 4.0, 6.0, 7.0, 8.0, 0.1, 0.2];
+        let (p, i, j) = max_prod_fast_real_with_value(&arr);
+        assert_eq!((i, j), max_prod_fast_real(&arr));
+        assert!((p - prod(&arr, i, j)).abs() < 1e-9 * p);
+    }
+
+    #[test]
+    fn test_subslice_real() {
+        let mut rng = test_rng();
+        for _ in 0..100 {
+            let a: Vec<f64> = Uniform::new(0.0, 2.0).sample_iter(&mut rng).take(30).collect();
+            let (p, _, _) = max_prod_fast_real_with_value(&a);
+            let s = max_prod_subslice_real(&a);
+            assert!((s.iter().product::<f64>() - p).abs() <= 1e-9 * p);
+        }
+
+        let a: Vec<f64> = vec![];
+        assert!(max_prod_subslice_real(&a).is_empty());
+    }
+
+    #[test]
+    fn test_real_empty() {
+        let a: Vec<f64> = vec![];
+        assert_eq!(try_max_prod_fast_real(&a), Err(MaxProdError::Empty));
+        assert_eq!(max_prod_fast_real(&a), (0, 0));
+        assert_eq!(min_prod_fast_real(&a), (0, 0));
+
+        let a = vec![0.5, 2.0, 3.0, 0.1];
+        assert_eq!(try_max_prod_fast_real(&a), Ok((1, 2)));
+        assert_eq!(try_max_prod_fast_real([f64::INFINITY]), Ok((0, 0)));
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::span::Span;
+
+/// The winning range `arr[start..=end]` together with its product.
+///
+/// ```
+/// use max_prod::{MaxProdResult, max_prod_fast_int_result};
+///
+/// let res = max_prod_fast_int_result([2u32, 0, 3, 4]);
+/// assert_eq!((res.start, res.end, res.product), (2, 3, 12));
+/// assert_eq!(res.len(), 2);
+/// assert_eq!(<(usize, usize)>::from(res), (2, 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaxProdResult<T> {
+    pub start: usize,
+    pub end: usize,
+    pub product: T,
+}
+
+impl<T> From<(T, usize, usize)> for MaxProdResult<T> {
+    fn from((product, start, end): (T, usize, usize)) -> Self {
+        MaxProdResult { start, end, product }
+    }
+}
+
+impl<T> MaxProdResult<T> {
+    /// Number of elements in the range, never zero.
+    #[allow(clippy::len_without_is_empty)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.end - self.start + 1
+    }
+
+    /// The range as a [`Span`], e.g. to slice the array with it.
+    #[must_use]
+    pub fn span(&self) -> Span {
+        Span::new(self.start, self.end)
+    }
+
+    /// Whether the range consists of a single element.
+    #[must_use]
+    pub fn is_single(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Drops the product, giving the `(start, end)` the plain functions return.
+impl<T> From<MaxProdResult<T>> for (usize, usize) {
+    fn from(res: MaxProdResult<T>) -> Self {
+        (res.start, res.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::max_prod::{max_prod_fast_int, max_prod_fast_int_result};
+    use super::*;
+
+    #[test]
+    fn test_from_with_value() {
+        let res = max_prod_fast_int_result([2u32, 0, 3, 4, 0, 5]);
+        assert_eq!(res, MaxProdResult { start: 2, end: 3, product: 12 });
+    }
+
+    #[test]
+    fn test_len_is_single() {
+        let res = max_prod_fast_int_result([2u32, 0, 3, 4, 0, 5]);
+        assert_eq!(res.len(), 2);
+        assert!(!res.is_single());
+
+        let res = max_prod_fast_int_result([2u32, 0, 1, 7]);
+        assert_eq!((res.start, res.end), (2, 3));
+        assert_eq!(res.len(), 2);
+
+        let res = max_prod_fast_int_result([0u32, 0]);
+        assert_eq!(res.len(), 1);
+        assert!(res.is_single());
+    }
+
+    #[test]
+    fn test_into_tuple() {
+        let arr = [2u32, 0, 3, 4, 0, 5];
+        let range: (usize, usize) = max_prod_fast_int_result(arr).into();
+        assert_eq!(range, max_prod_fast_int(arr));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let res = max_prod_fast_int_result([2u32, 0, 3, 4, 0, 5]);
+        let json = serde_json::to_string(&res).unwrap();
+        assert_eq!(json, r#"{"start":2,"end":3,"product":12}"#);
+
+        let back: MaxProdResult<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, res);
+
+        let res = crate::max_prod_fast_real_result([0.5f64, 4.0, 0.5, 3.0]);
+        let back: MaxProdResult<f64> = serde_json::from_str(&serde_json::to_string(&res).unwrap()).unwrap();
+        assert_eq!(back, res);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::int::MaxProdInt;
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) for a run-length encoded array of
+/// `(value, count)` pairs, `count` consecutive elements are `value`.
+///
+/// The indices are those of the expanded array and ties are broken the same way, so the
+/// result is exactly that of `max_prod_fast_int` on the expanded array. Every pair is
+/// multiplied in at once with `value.pow(count)`, so the running time only depends on the
+/// number of pairs. Pairs with a count of zero are skipped.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_rle;
+///
+/// // 2 2 2 0 0 3 3
+/// assert_eq!(max_prod_fast_int_rle(&[(2u32, 3), (0, 2), (3, 2)]), (5, 6));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_rle<T: MaxProdInt>(runs: &[(T, usize)]) -> (usize, usize) {
+    let mut max_prod = T::zero();
+    let mut max = (0, 0);
+
+    let mut current_start = 0;
+    let mut current_prod = T::zero();
+    let mut i = 0; // index of the first element of the pair in the expanded array
+
+    for &(value, count) in runs.iter().filter(|&&(_, count)| count > 0) {
+        if value == T::zero() {
+            current_prod = T::zero();
+        } else {
+            if current_prod == T::zero() {
+                current_prod = T::one();
+                current_start = i;
+            }
+
+            // ones don't change the product, so only their first element can be the first
+            // to beat the maximum, otherwise every element grows the product and the last
+            // one is the largest
+            let end = if value == T::one() { i } else { i + count - 1 };
+            current_prod = current_prod * num::pow(value, count);
+
+            if current_prod > max_prod {
+                max = (current_start, end);
+                max_prod = current_prod;
+            }
+        }
+
+        i += count;
+    }
+
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::max_prod_fast_int;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn expand(runs: &[(u64, usize)]) -> Vec<u64> {
+        runs.iter().flat_map(|&(value, count)| core::iter::repeat_n(value, count)).collect()
+    }
+
+    #[test]
+    fn test_rle_basic() {
+        assert_eq!(max_prod_fast_int_rle::<u32>(&[]), (0, 0));
+        assert_eq!(max_prod_fast_int_rle(&[(0u32, 4)]), (0, 0));
+        assert_eq!(max_prod_fast_int_rle(&[(0u32, 2), (1, 3)]), (2, 2));
+        assert_eq!(max_prod_fast_int_rle(&[(1u32, 2), (5, 0), (3, 2), (1, 4)]), (0, 3));
+        assert_eq!(max_prod_fast_int_rle(&[(4u32, 1), (0, 1), (2, 2)]), (0, 0));
+    }
+
+    #[test]
+    fn test_random_rle() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(0..10);
+            let runs: Vec<(u64, usize)> = (0..n)
+                .map(|_| (Uniform::new_inclusive(0, 3).sample(&mut rng), rng.gen_range(0..5)))
+                .collect();
+
+            assert_eq!(max_prod_fast_int_rle(&runs), max_prod_fast_int(expand(&runs)), "{runs:?}");
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use alloc::vec::Vec;
+
+use num::traits::Num;
+
+/// The runs of non-zero elements between the zeros of `arr`, as inclusive ranges sorted by
+/// start. These are the ranges [`max_prod_fast_int`](crate::max_prod_fast_int) scans.
+///
+/// ```
+/// use max_prod::positive_runs_int;
+///
+/// assert_eq!(positive_runs_int([0u32, 2, 3, 0, 0, 5]), vec![(1, 2), (5, 5)]);
+/// ```
+#[must_use]
+pub fn positive_runs_int<T: Num + Copy>(arr: impl AsRef<[T]>) -> Vec<(usize, usize)> {
+    let arr = arr.as_ref();
+    let n = arr.len();
+    let mut runs = Vec::new();
+
+    let mut i = 0;
+    while i < n {
+        if arr[i] == T::zero() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < n && arr[i] != T::zero() {
+            i += 1;
+        }
+        runs.push((start, i - 1));
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_zeros() {
+        assert_eq!(positive_runs_int([0u32, 0, 1, 2]), vec![(2, 3)]);
+        assert_eq!(positive_runs_int([1u32, 2, 0, 0]), vec![(0, 1)]);
+        assert_eq!(positive_runs_int([3u32, 0, 0, 0, 4, 0, 5]), vec![(0, 0), (4, 4), (6, 6)]);
+        assert_eq!(positive_runs_int([0u32, 7, 7, 7, 0]), vec![(1, 3)]);
+    }
+
+    #[test]
+    fn test_runs_edge_cases() {
+        assert_eq!(positive_runs_int::<u32>([]), vec![]);
+        assert_eq!(positive_runs_int([0u32, 0]), vec![]);
+        assert_eq!(positive_runs_int([1u32, 1, 1]), vec![(0, 2)]);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::int::MaxProdInt;
+
+/// The score used by [`max_prod_fast_int`](crate::max_prod_fast_int), just the product.
+///
+/// ```
+/// use max_prod::{product_score, max_prod_by_fast_int, max_prod_fast_int};
+///
+/// let a = [2u32, 0, 3, 4];
+/// assert_eq!(max_prod_by_fast_int(a, product_score), max_prod_fast_int(a));
+/// ```
+#[must_use]
+pub fn product_score<T>(prod: T, _len: usize) -> T {
+    prod
+}
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but maximizes `score(product, length)`
+/// instead of the product.
+///
+/// Only ranges without zeros are considered, `(0, 0)` is returned if there is none. On
+/// ties the range with the smallest start and then the smallest end wins, so with
+/// [`product_score`] this returns the same range as `max_prod_fast_int`. Since the score
+/// can be anything every range inside a run of non-zero elements has to be checked,
+/// which is quadratic in the length of the longest run.
+///
+/// ```
+/// use max_prod::max_prod_by_fast_int;
+///
+/// // the longest range with a product of at least four
+/// let a = [1u32, 2, 2, 1, 0, 9];
+/// assert_eq!(max_prod_by_fast_int(a, |p, len| (p >= 4, len)), (0, 3));
+/// ```
+#[must_use]
+pub fn max_prod_by_fast_int<T, S, F>(arr: impl AsRef<[T]>, score: F) -> (usize, usize)
+where
+    T: MaxProdInt,
+    S: PartialOrd,
+    F: Fn(T, usize) -> S,
+{
+    let arr = arr.as_ref();
+    let n = arr.len();
+    let mut best: Option<(S, usize, usize)> = None;
+
+    for i in 0..n {
+        let mut prod = T::one();
+
+        for (j, &x) in arr.iter().enumerate().skip(i) {
+            if x == T::zero() {
+                break;
+            }
+            prod = prod * x;
+
+            let key = score(prod, j - i + 1);
+            if best.as_ref().is_none_or(|(b, _, _)| key > *b) {
+                best = Some((key, i, j));
+            }
+        }
+    }
+
+    best.map_or((0, 0), |(_, i, j)| (i, j))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::max_prod_fast_int;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_by_product_per_length() {
+        let arr = [1u64, 1, 5, 0, 2, 3];
+        assert_eq!(max_prod_by_fast_int(arr, product_score), (4, 5));
+        assert_eq!(max_prod_by_fast_int(arr, |p, len| p as f64 / len as f64), (2, 2));
+
+        // shortest range among the ones with the largest product
+        let arr = [1u64, 2, 1, 0, 2];
+        assert_eq!(max_prod_by_fast_int(arr, |p, len| (p, usize::MAX - len)), (1, 1));
+    }
+
+    #[test]
+    fn test_by_zeros() {
+        assert_eq!(max_prod_by_fast_int([0u32, 0], product_score), (0, 0));
+        assert_eq!(max_prod_by_fast_int([0u32, 3], product_score), (1, 1));
+        assert_eq!(max_prod_by_fast_int([] as [u32; 0], product_score), (0, 0));
+    }
+
+    #[test]
+    fn test_random_by_default_score() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(20).collect();
+            assert_eq!(max_prod_by_fast_int(&a, product_score), max_prod_fast_int(&a));
+        }
+    }
+}
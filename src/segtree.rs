@@ -0,0 +1,225 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::traits::real::Real;
+
+#[derive(Debug, Clone, Copy)]
+struct Node<T> {
+    total: T,
+    // best range starting at the first element of the node, (product, end)
+    prefix: (T, usize),
+    // best range ending at the last element of the node, (product, start)
+    suffix: (T, usize),
+    // best range of the node, (product, start, end)
+    best: (T, usize, usize),
+}
+
+impl<T: Real + Copy> Node<T> {
+    fn leaf(idx: usize, x: T) -> Self {
+        Node { total: x, prefix: (x, idx), suffix: (x, idx), best: (x, idx, idx) }
+    }
+
+    /// On ties the range with the smaller start and then the smaller end wins, that is
+    /// the one [`max_prod_brute_force`](crate::max_prod_brute_force) finds first.
+    fn merge(l: &Self, r: &Self) -> Self {
+        let prefix = {
+            let p = l.total * r.prefix.0;
+            if p > l.prefix.0 { (p, r.prefix.1) } else { l.prefix }
+        };
+
+        let suffix = {
+            let p = l.suffix.0 * r.total;
+            if p >= r.suffix.0 { (p, l.suffix.1) } else { r.suffix }
+        };
+
+        let crossing = (l.suffix.0 * r.prefix.0, l.suffix.1, r.prefix.1);
+        let mut best = l.best;
+        for c in [crossing, r.best] {
+            if c.0 > best.0 || (c.0 == best.0 && (c.1, c.2) < (best.1, best.2)) {
+                best = c;
+            }
+        }
+
+        Node { total: l.total * r.total, prefix, suffix, best }
+    }
+}
+
+/// Segment tree over non-negative reals that keeps track of the range with the maximum
+/// product while single elements are changed.
+///
+/// [`update`](Self::update) takes O(log n) and [`best`](Self::best) O(1), the result
+/// is the same range [`max_prod_fast_real`](crate::max_prod_fast_real) would return
+/// for the current values, exact ties included.
+///
+/// ```
+/// use max_prod::MaxProdSegTree;
+///
+/// let mut t = MaxProdSegTree::new([0.5, 2.0, 3.0, 0.1]);
+/// assert_eq!(t.best(), (1, 2));
+///
+/// t.update(3, 4.0);
+/// assert_eq!(t.best(), (1, 3));
+/// assert_eq!(t.best_prod(), 24.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxProdSegTree<T> {
+    n: usize,
+    size: usize,
+    // 1-based heap layout, leaves start at `size`, `None` is used for the padding leaves
+    tree: Vec<Option<Node<T>>>,
+}
+
+impl<T: Real + Copy> MaxProdSegTree<T> {
+    pub fn new(arr: impl AsRef<[T]>) -> Self {
+        let arr = arr.as_ref();
+        let n = arr.len();
+        let size = n.next_power_of_two();
+        let mut tree = vec![None; 2 * size];
+
+        for (i, &x) in arr.iter().enumerate() {
+            tree[size + i] = Some(Node::leaf(i, x));
+        }
+
+        let mut seg = MaxProdSegTree { n, size, tree };
+        for k in (1..size).rev() {
+            seg.pull(k);
+        }
+
+        seg
+    }
+
+    fn pull(&mut self, k: usize) {
+        self.tree[k] = match (&self.tree[2 * k], &self.tree[2 * k + 1]) {
+            (Some(l), Some(r)) => Some(Node::merge(l, r)),
+            (Some(l), None) => Some(*l),
+            (None, _) => None,
+        };
+    }
+
+    /// Sets element `idx` to `value`, panics if `idx` is out of bounds.
+    pub fn update(&mut self, idx: usize, value: T) {
+        assert!(idx < self.n, "index {} out of bounds for length {}", idx, self.n);
+
+        let mut k = self.size + idx;
+        self.tree[k] = Some(Node::leaf(idx, value));
+
+        while k > 1 {
+            k /= 2;
+            self.pull(k);
+        }
+    }
+
+    /// The range with the maximum product, `(0, 0)` if the tree is empty.
+    pub fn best(&self) -> (usize, usize) {
+        self.root().map_or((0, 0), |node| (node.best.1, node.best.2))
+    }
+
+    /// The product of [`best`](Self::best).
+    pub fn best_prod(&self) -> T {
+        self.root().map_or(T::zero(), |node| node.best.0)
+    }
+
+    fn root(&self) -> Option<&Node<T>> {
+        // for a single element the root is the leaf itself
+        self.tree[1].as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::max_prod_brute_force_improved;
+    use crate::real::max_prod_fast_real;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_segtree_basic() {
+        let mut t = MaxProdSegTree::new([0.5, 4.0, 0.5, 3.0, 0.1]);
+        assert_eq!(t.best(), (1, 3));
+        assert_eq!(t.best_prod(), 6.0);
+
+        t.update(2, 0.1);
+        assert_eq!(t.best(), (1, 1));
+
+        t.update(4, 5.0);
+        assert_eq!(t.best(), (3, 4));
+        assert_eq!(t.len(), 5);
+
+        let t = MaxProdSegTree::<f64>::new([]);
+        assert!(t.is_empty());
+        assert_eq!(t.best(), (0, 0));
+
+        let t = MaxProdSegTree::new([0.5]);
+        assert_eq!(t.best(), (0, 0));
+        assert_eq!(t.best_prod(), 0.5);
+    }
+
+    #[test]
+    fn test_segtree_ties() {
+        let mut t = MaxProdSegTree::new([1.5, 0.5, 2.0, 0.0, 2.0]);
+        assert_eq!(t.best(), (2, 2));
+        assert_eq!(t.best(), max_prod_fast_real([1.5, 0.5, 2.0, 0.0, 2.0]));
+
+        t.update(3, 0.5);
+        assert_eq!(t.best(), (2, 2));
+        assert_eq!(t.best(), max_prod_fast_real([1.5, 0.5, 2.0, 0.5, 2.0]));
+    }
+
+    #[test]
+    fn test_random_segtree_ties() {
+        let mut rng = test_rng();
+        // powers of two multiply exactly, so ties are exact and common
+        let values = [0.0, 0.5, 1.0, 2.0, 4.0];
+
+        for _ in 0..100 {
+            let n = rng.gen_range(1..12);
+            let mut a: Vec<f64> = (0..n).map(|_| values[rng.gen_range(0..values.len())]).collect();
+            let mut t = MaxProdSegTree::new(&a);
+
+            for _ in 0..20 {
+                let idx = rng.gen_range(0..n);
+                a[idx] = values[rng.gen_range(0..values.len())];
+                t.update(idx, a[idx]);
+
+                assert_eq!(t.best(), max_prod_fast_real(&a), "{a:?}");
+                assert_eq!(t.best(), max_prod_brute_force_improved(&a), "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_segtree() {
+        let mut rng = test_rng();
+        let dist = Uniform::new(0.0, 2.0);
+
+        for _ in 0..100 {
+            let n = rng.gen_range(1..40);
+            let mut a: Vec<f64> = dist.sample_iter(&mut rng).take(n).collect();
+            let mut t = MaxProdSegTree::new(&a);
+            assert_eq!(t.best(), max_prod_fast_real(&a));
+
+            for _ in 0..50 {
+                let idx = rng.gen_range(0..n);
+                // some zeros, so that the tie-breaking between zero products is covered
+                a[idx] = if rng.gen_bool(0.1) { 0.0 } else { rng.sample(dist) };
+                t.update(idx, a[idx]);
+
+                assert_eq!(t.best(), max_prod_fast_real(&a));
+                assert_eq!(t.best(), max_prod_brute_force_improved(&a));
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::accumulator::MaxProdAccumulator;
+use crate::auto::max_prod_auto_int;
+use crate::divide_conquer::max_prod_divide_conquer_real;
+use crate::log_domain::{max_prod_fast_real_log, max_prod_fast_real_subnormal_safe, MaxProdRealTracker};
+use crate::max_prod::{
+    max_abs_prod_fast_int,
+    max_prod_brute_force,
+    max_prod_brute_force_checked,
+    max_prod_brute_force_improved,
+    max_prod_brute_force_improved_checked,
+    max_prod_fast_int,
+    max_prod_fast_int_checked,
+    max_prod_fast_int_saturating,
+    max_prod_fast_int_signed,
+};
+use crate::real::{max_prod_fast_real, max_prod_fast_real_inplace, max_prod_kadane_real};
+use crate::real_f64::max_prod_fast_real_f64;
+use crate::score::{max_prod_by_fast_int, product_score};
+use crate::tie_break::{max_prod_fast_int_with_tiebreak, TieBreak};
+use crate::tracker::MaxProdIntTracker;
+use crate::widen::max_prod_fast_int_widening;
+
+type Algorithm<T> = (&'static str, fn(&[T]) -> (usize, usize));
+
+/// Known good results, a change in any of them has to be deliberate. Every algorithm in
+/// the group has to return the range of every case.
+const INT_CASES: &[(&[u64], (usize, usize))] = &[
+    (&[2, 3, 0, 6, 1, 1], (0, 1)),
+    (&[0, 0, 3, 0, 2, 2, 0, 0, 5, 0], (8, 8)),
+    (&[1, 1, 0, 1, 2, 1, 3, 1, 0, 1], (3, 6)),
+    (&[4, 0, 2, 2, 0, 4], (0, 0)),
+    (&[0, 7, 0, 0, 1, 7, 1, 0, 7], (1, 1)),
+    (&[0, 0, 0], (0, 0)),
+    (&[1, 1, 1], (0, 0)),
+    (&[5], (0, 0)),
+    (&[], (0, 0)),
+];
+
+const SIGNED_CASES: &[(&[i64], (usize, usize))] = &[
+    (&[-2, 3, -4], (0, 2)),
+    (&[2, -1, 0, -3, -4, 0, 5], (3, 4)),
+    (&[-3, 0, -2, -1, 0, 6], (5, 5)),
+    (&[0, -5, 0], (0, 0)),
+    (&[1, -2, -3, 1, -1], (0, 2)),
+];
+
+const REAL_CASES: &[(&[f64], (usize, usize))] = &[
+    // near one, 1.05 * 1.02 = 1.071 beats 1.05 * 1.02 * 0.99 * 1.01 = 1.0708929
+    (&[0.97, 1.05, 1.02, 0.99, 1.01, 0.90], (1, 2)),
+    (&[0.97, 1.05, 1.02, 0.99, 1.03, 0.90], (1, 4)),
+    (&[0.999, 1.001, 1.002, 0.9995, 1.0004, 0.998], (1, 2)),
+    (&[0.9, 0.9, 1.05, 0.9], (2, 2)),
+    (&[0.5, 2.0, 0.0, 3.0, 0.9, 4.0], (3, 5)),
+    (&[0.1, 0.5, 13.0, 2.0, 0.1, 4.0, 6.0, 7.0, 8.0, 0.1, 0.2], (2, 8)),
+    (&[2.0, 0.5, 3.0, 0.25, 4.0], (4, 4)),
+    (&[1.1, 2.0, 3.0, 1.5], (0, 3)),
+    (&[0.5, 0.25], (0, 0)),
+    (&[0.0, 0.0], (0, 0)),
+];
+
+fn int_algorithms() -> Vec<Algorithm<u64>> {
+    vec![
+        ("brute force", |a| max_prod_brute_force(a)),
+        ("brute force improved", |a| max_prod_brute_force_improved(a)),
+        ("brute force checked", |a| max_prod_brute_force_checked(a).unwrap()),
+        ("brute force improved checked", |a| max_prod_brute_force_improved_checked(a).unwrap()),
+        ("fast int", |a| max_prod_fast_int(a)),
+        ("fast int checked", |a| max_prod_fast_int_checked(a).unwrap()),
+        ("fast int saturating", |a| max_prod_fast_int_saturating(a)),
+        ("fast int widening", |a| max_prod_fast_int_widening(a)),
+        ("fast int tie break", |a| max_prod_fast_int_with_tiebreak(a, TieBreak::Leftmost)),
+        ("fast int by score", |a| max_prod_by_fast_int(a, product_score)),
+        ("auto int", |a| max_prod_auto_int(a)),
+        ("int tracker", |a| {
+            let mut t = MaxProdIntTracker::new();
+            t.extend(a.iter().copied());
+            t.best()
+        }),
+        ("accumulator", |a| {
+            let mut acc = MaxProdAccumulator::new();
+            acc.consider(a, 0);
+            acc.best()
+        }),
+    ]
+}
+
+fn signed_algorithms() -> Vec<Algorithm<i64>> {
+    vec![
+        ("brute force", |a| max_prod_brute_force(a)),
+        ("brute force improved", |a| max_prod_brute_force_improved(a)),
+        ("fast int signed", |a| max_prod_fast_int_signed(a)),
+    ]
+}
+
+fn real_algorithms() -> Vec<Algorithm<f64>> {
+    #[allow(unused_mut)]
+    let mut algorithms: Vec<Algorithm<f64>> = vec![
+        ("brute force", |a| max_prod_brute_force(a)),
+        ("brute force improved", |a| max_prod_brute_force_improved(a)),
+        ("fast real", |a| max_prod_fast_real(a)),
+        ("fast real f64", |a| max_prod_fast_real_f64(a)),
+        ("fast real inplace", |a| max_prod_fast_real_inplace(a, &mut Vec::new())),
+        ("fast real log", max_prod_fast_real_log),
+        ("fast real subnormal safe", max_prod_fast_real_subnormal_safe),
+        ("real tracker", |a| {
+            let mut t = MaxProdRealTracker::new();
+            t.extend(a.iter().copied());
+            t.best()
+        }),
+        ("kadane real", |a| max_prod_kadane_real(a)),
+        ("divide and conquer real", max_prod_divide_conquer_real),
+    ];
+
+    #[cfg(feature = "hp")]
+    algorithms.push(("fast real hp", |a| crate::hp::max_prod_fast_real_hp(a)));
+    #[cfg(feature = "rayon")]
+    algorithms.push(("fast real par", crate::par::max_prod_fast_real_par));
+    #[cfg(feature = "rayon")]
+    algorithms.push(("brute force par", crate::par::max_prod_brute_force_par));
+
+    algorithms
+}
+
+/// Runs every algorithm on every case and returns a line for each mismatch, so a failing
+/// run shows all changed results at once.
+fn replay<T: core::fmt::Debug>(cases: &[(&[T], (usize, usize))], algorithms: &[Algorithm<T>]) -> Vec<String> {
+    let mut mismatches = Vec::new();
+
+    for (arr, expected) in cases {
+        for (name, algorithm) in algorithms {
+            let got = algorithm(arr);
+            if got != *expected {
+                mismatches.push(format!("{name} on {arr:?}: expected {expected:?}, got {got:?}"));
+            }
+        }
+    }
+
+    mismatches
+}
+
+#[test]
+fn snapshot_int() {
+    let mismatches = replay(INT_CASES, &int_algorithms());
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
+
+#[test]
+fn snapshot_signed() {
+    let mismatches = replay(SIGNED_CASES, &signed_algorithms());
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+
+    // the largest absolute product, which is negative here
+    assert_eq!(max_abs_prod_fast_int([2i64, -3, 0, 5]), (0, 1));
+}
+
+#[test]
+fn snapshot_real() {
+    let mismatches = replay(REAL_CASES, &real_algorithms());
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
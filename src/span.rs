@@ -0,0 +1,167 @@
+use core::ops::{Range, RangeInclusive};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::result::MaxProdResult;
+
+/// The range `arr[start..=end_inclusive]` returned as `(start, end)` by the algorithms.
+///
+/// Both ends of the tuples are inclusive, so slicing with `arr[i..j]` silently drops the
+/// last element. Every algorithm result converts into a `Span` with `into()`, which
+/// slices correctly, and the main algorithms have `_span` variants returning one.
+///
+/// ```
+/// use max_prod::{max_prod_fast_int, Span};
+///
+/// let arr = [2u32, 0, 3, 4];
+/// let span: Span = max_prod_fast_int(arr).into();
+/// assert_eq!(span.slice(&arr), &[3, 4]);
+/// assert_eq!(span.range(), 2..=3);
+/// assert_eq!(span.range_exclusive(), 2..4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Span {
+    pub start: usize,
+    pub end_inclusive: usize,
+}
+
+impl Span {
+    #[must_use]
+    pub fn new(start: usize, end_inclusive: usize) -> Self {
+        Span { start, end_inclusive }
+    }
+
+    /// `start..=end_inclusive`
+    #[must_use]
+    pub fn range(&self) -> RangeInclusive<usize> {
+        self.start..=self.end_inclusive
+    }
+
+    /// `start..end_exclusive`, the same elements as [`range`](Self::range).
+    #[must_use]
+    pub fn range_exclusive(&self) -> Range<usize> {
+        self.start..self.end_exclusive()
+    }
+
+    /// One past the last element.
+    #[must_use]
+    pub fn end_exclusive(&self) -> usize {
+        self.end_inclusive + 1
+    }
+
+    /// Number of elements in the span, never zero.
+    #[allow(clippy::len_without_is_empty)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.end_inclusive - self.start + 1
+    }
+
+    /// The elements of `arr` in the span, panics if it is out of bounds.
+    #[must_use]
+    pub fn slice<'a, T>(&self, arr: &'a [T]) -> &'a [T] {
+        &arr[self.range()]
+    }
+}
+
+impl From<(usize, usize)> for Span {
+    fn from((start, end_inclusive): (usize, usize)) -> Self {
+        Span { start, end_inclusive }
+    }
+}
+
+impl From<Span> for (usize, usize) {
+    fn from(span: Span) -> Self {
+        (span.start, span.end_inclusive)
+    }
+}
+
+impl<T> From<MaxProdResult<T>> for Span {
+    fn from(res: MaxProdResult<T>) -> Self {
+        Span { start: res.start, end_inclusive: res.end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use crate::max_prod::{
+        max_prod_brute_force_improved_span,
+        max_prod_brute_force_span,
+        max_prod_brute_force_with_value,
+        max_prod_fast_int,
+        max_prod_fast_int_result,
+        max_prod_fast_int_span,
+        max_prod_subslice_int,
+    };
+    use crate::real::max_prod_fast_real_span;
+    use crate::test_rng::test_rng;
+    use super::*;
+
+    #[test]
+    fn test_slice() {
+        let arr = [2u32, 0, 3, 4, 0, 5];
+
+        assert_eq!(Span::new(2, 3).slice(&arr), &[3, 4]);
+        assert_eq!(Span::new(0, 0).slice(&arr), &[2]);
+        assert_eq!(Span::new(5, 5).slice(&arr), &[5]);
+        assert_eq!(Span::new(0, 5).slice(&arr), &arr[..]);
+
+        let span: Span = max_prod_fast_int(arr).into();
+        assert_eq!(span.slice(&arr), max_prod_subslice_int(&arr));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_out_of_bounds() {
+        let _ = Span::new(2, 3).slice(&[1u32, 2, 3]);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let span = Span::new(2, 3);
+        assert_eq!(span.range(), 2..=3);
+        assert_eq!(span.range_exclusive(), 2..4);
+        assert_eq!(span.end_exclusive(), 4);
+        assert_eq!(span.len(), 2);
+        assert_eq!(span.range().count(), span.len());
+        assert_eq!(span.range_exclusive().len(), span.len());
+
+        let single = Span::new(7, 7);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.range_exclusive(), 7..8);
+        assert_eq!(single.range().collect::<Vec<_>>(), vec![7]);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Span::from((1, 4)), Span::new(1, 4));
+        assert_eq!(<(usize, usize)>::from(Span::new(1, 4)), (1, 4));
+
+        let res = max_prod_fast_int_result([2u32, 0, 3, 4]);
+        assert_eq!(Span::from(res), Span::new(2, 3));
+        assert_eq!(res.span(), Span::new(2, 3));
+    }
+
+    #[test]
+    fn test_span_variants() {
+        let mut rng = test_rng();
+
+        for _ in 0..200 {
+            let len = rng.gen_range(1..30);
+            let a: Vec<u32> = (0..len).map(|_| rng.gen_range(0..4)).collect();
+            let (p, _, _) = max_prod_brute_force_with_value(&a);
+            let p = if p == 0 { a[0] } else { p };
+
+            for span in [max_prod_brute_force_span(&a), max_prod_brute_force_improved_span(&a), max_prod_fast_int_span(&a)] {
+                assert_eq!(span.slice(&a).iter().product::<u32>(), p, "{a:?}");
+            }
+
+            let r: Vec<f64> = a.iter().map(|&x| f64::from(x)).collect();
+            let span = max_prod_fast_real_span(&r);
+            assert_eq!(span.slice(&r).iter().product::<f64>(), f64::from(p), "{a:?}");
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Seed of the randomized tests unless `MAX_PROD_SEED` is set.
+const DEFAULT_SEED: u64 = 20_261_014;
+
+/// Seeded rng for the randomized tests.
+///
+/// The seed is fixed, so every run tests the same arrays. `MAX_PROD_SEED` overrides it, e.g.
+/// to try other arrays locally. It is printed, so it shows up in the output of a failing
+/// test and the failure can be reproduced by running the test again with `MAX_PROD_SEED`
+/// set to it.
+pub(crate) fn test_rng() -> StdRng {
+    let seed = match std::env::var("MAX_PROD_SEED") {
+        Ok(s) => s.parse().expect("MAX_PROD_SEED must be a u64"),
+        Err(_) => DEFAULT_SEED,
+    };
+
+    println!("rng seed: {seed}, rerun with MAX_PROD_SEED={seed}");
+    StdRng::seed_from_u64(seed)
+}
@@ -0,0 +1,129 @@
+
+use num::traits::CheckedMul;
+
+use crate::int::MaxProdInt;
+
+/// The first range whose product reaches `t`, or `None` if there is none.
+///
+/// "First" means the range that ends first, and among the ranges ending there the
+/// shortest one. Products only grow when a range is extended inside a run of non-zero
+/// elements, so a window is slid over each run and shrunk from the left as soon as its
+/// product reaches `t`. If `t` is zero the first element is returned.
+///
+/// A product that overflows `T` is larger than `t`, so it reaches `t` as well. Only the
+/// products of ranges that don't reach `t` are kept, those always fit.
+///
+/// ```
+/// use max_prod::first_prod_exceeding_int;
+///
+/// assert_eq!(first_prod_exceeding_int([2u32, 3, 0, 4, 5], 6), Some((0, 1)));
+/// assert_eq!(first_prod_exceeding_int([2u32, 3, 0, 4, 5], 20), Some((3, 4)));
+/// assert_eq!(first_prod_exceeding_int([2u32, 3, 0, 4, 5], 21), None);
+/// ```
+#[must_use]
+pub fn first_prod_exceeding_int<T: MaxProdInt + CheckedMul>(arr: impl AsRef<[T]>, t: T) -> Option<(usize, usize)> {
+    let arr = arr.as_ref();
+
+    if t == T::zero() {
+        return if arr.is_empty() { None } else { Some((0, 0)) };
+    }
+
+    let mut start = 0;
+    let mut window_prod = T::one();
+
+    for j in 0..arr.len() {
+        if arr[j] == T::zero() {
+            start = j + 1;
+            window_prod = T::one();
+            continue;
+        }
+
+        // window_prod is the product of arr[start..j]
+        let reaches = |p: T| p.checked_mul(&arr[j]).is_none_or(|p| p >= t);
+
+        if reaches(window_prod) {
+            while start < j && reaches(window_prod / arr[start]) {
+                window_prod = window_prod / arr[start];
+                start += 1;
+            }
+            return Some((start, j));
+        }
+
+        window_prod = window_prod * arr[j];
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::{max_prod_fast_int_with_value, prod};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn first_prod_exceeding_brute_force(arr: &[u64], t: u64) -> Option<(usize, usize)> {
+        (0..arr.len()).find_map(|j| (0..=j).rev().find(|&i| prod(arr, i, j) >= t).map(|i| (i, j)))
+    }
+
+    #[test]
+    fn test_threshold_around_max() {
+        let arr = [1u64, 2, 1, 3, 0, 7, 0, 2, 2, 2];
+        let (max, i, j) = max_prod_fast_int_with_value(arr);
+        assert_eq!((max, i, j), (8, 7, 9));
+
+        assert_eq!(first_prod_exceeding_int(arr, 2), Some((1, 1)));
+        assert_eq!(first_prod_exceeding_int(arr, 6), Some((1, 3)));
+        assert_eq!(first_prod_exceeding_int(arr, 7), Some((5, 5)));
+        assert_eq!(first_prod_exceeding_int(arr, max), Some((i, j)));
+        assert_eq!(first_prod_exceeding_int(arr, max + 1), None);
+    }
+
+    #[test]
+    fn test_threshold_edge_cases() {
+        assert_eq!(first_prod_exceeding_int::<u64>([], 1), None);
+        assert_eq!(first_prod_exceeding_int::<u64>([], 0), None);
+        assert_eq!(first_prod_exceeding_int([0u64, 0], 0), Some((0, 0)));
+        assert_eq!(first_prod_exceeding_int([0u64, 0], 1), None);
+        assert_eq!(first_prod_exceeding_int([0u64, 1, 1], 1), Some((1, 1)));
+        // the ones in front are dropped to get the shortest range
+        assert_eq!(first_prod_exceeding_int([1u64, 1, 5], 5), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_threshold_overflow() {
+        // 16 * 16 = 256 doesn't fit into a u8 but is larger than any threshold
+        assert_eq!(first_prod_exceeding_int([16u8, 16], 200), Some((0, 1)));
+        assert_eq!(first_prod_exceeding_int([16u8, 16], u8::MAX), Some((0, 1)));
+        assert_eq!(first_prod_exceeding_int([3u8, 5, 17, 5], u8::MAX), Some((0, 2)));
+        assert_eq!(first_prod_exceeding_int([3u8, 5, 16, 4], 254), Some((1, 3)));
+        assert_eq!(first_prod_exceeding_int([1u8, 255, 2], u8::MAX), Some((1, 1)));
+        assert_eq!(first_prod_exceeding_int([2u8, 127, 0, 127, 2], u8::MAX), None);
+        assert_eq!(first_prod_exceeding_int([2u8, 127, 0, 127, 3], u8::MAX), Some((3, 4)));
+        assert_eq!(first_prod_exceeding_int([u64::MAX, 2], u64::MAX), Some((0, 0)));
+        assert_eq!(first_prod_exceeding_int([1 << 32, 1 << 32, 3], u64::MAX), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_random_threshold() {
+        let mut rng = test_rng();
+
+        for _ in 0..500 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(20).collect();
+            let t = rng.gen_range(0..=300);
+            assert_eq!(first_prod_exceeding_int(&a, t), first_prod_exceeding_brute_force(&a, t), "{a:?} {t}");
+        }
+
+        // u8 products overflow all the time, the brute force multiplies in u64
+        for _ in 0..500 {
+            let a: Vec<u8> = Uniform::new_inclusive(0, 40).sample_iter(&mut rng).take(10).collect();
+            let wide: Vec<u64> = a.iter().map(|&x| x.into()).collect();
+            let t = rng.gen_range(200..=u8::MAX);
+            assert_eq!(first_prod_exceeding_int(&a, t), first_prod_exceeding_brute_force(&wide, t.into()), "{a:?} {t}");
+        }
+    }
+}
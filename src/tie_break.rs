@@ -0,0 +1,273 @@
+use crate::int::MaxProdInt;
+use crate::max_prod::{best_or_default, max_prod_fast_int_with_value, prod};
+
+/// Decides which range wins if several ranges share the maximum product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Whatever the scan finds first, this is what [`max_prod_fast_int`](crate::max_prod_fast_int) returns.
+    /// For the integer algorithm this is the same as `Leftmost`.
+    #[default]
+    FirstFound,
+    /// The shortest range, the leftmost one if several are equally short.
+    Shortest,
+    /// The longest range, the leftmost one if several are equally long.
+    Longest,
+    /// The range with the smallest start, then the smallest end.
+    Leftmost,
+    /// The range with the largest end, then the largest start.
+    Rightmost,
+}
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but lets the caller decide
+/// which range wins on ties, see [`max_prod_fast_int_rightmost`] for a faster
+/// `TieBreak::Rightmost`.
+///
+/// ```
+/// use max_prod::{max_prod_fast_int_with_tiebreak, TieBreak};
+///
+/// // [0 .. 0], [2 .. 3] and [2 .. 4] all have a product of six
+/// let a = [6u32, 0, 2, 3, 1];
+/// assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Leftmost), (0, 0));
+/// assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Longest), (2, 4));
+/// assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Rightmost), (2, 4));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_with_tiebreak<T: MaxProdInt>(arr: &[T], tie_break: TieBreak) -> (usize, usize) {
+    let (max_prod, i, j) = max_prod_fast_int_with_value(arr);
+
+    if tie_break == TieBreak::FirstFound || max_prod == T::zero() {
+        return (i, j);
+    }
+
+    let n = arr.len();
+    let mut best: Option<(usize, usize)> = None;
+    let mut s = 0;
+
+    while s < n {
+        if arr[s] == T::zero() {
+            s += 1;
+            continue;
+        }
+
+        let mut e = s;
+        while e + 1 < n && arr[e + 1] != T::zero() {
+            e += 1;
+        }
+
+        if prod(arr, s, e) == max_prod {
+            // only ones can be trimmed from either side of a run without changing
+            // its product, so every range starting in s..=a and ending in b..=e ties
+            let candidate = match (s..=e).find(|&k| arr[k] != T::one()) {
+                Some(a) => {
+                    let b = (s..=e).rev().find(|&k| arr[k] != T::one()).unwrap();
+
+                    match tie_break {
+                        TieBreak::FirstFound | TieBreak::Leftmost => (s, b),
+                        TieBreak::Shortest => (a, b),
+                        TieBreak::Longest => (s, e),
+                        TieBreak::Rightmost => (a, e),
+                    }
+                }
+                None => match tie_break { // only ones, any range of the run ties
+                    TieBreak::Longest => (s, e),
+                    TieBreak::Rightmost => (e, e),
+                    _ => (s, s),
+                },
+            };
+
+            let better = match (best, tie_break) {
+                (None, _) => true,
+                (Some(_), TieBreak::FirstFound | TieBreak::Leftmost) => false,
+                (Some((x, y)), TieBreak::Shortest) => candidate.1 - candidate.0 < y - x,
+                (Some((x, y)), TieBreak::Longest) => candidate.1 - candidate.0 > y - x,
+                (Some(_), TieBreak::Rightmost) => true,
+            };
+
+            if better {
+                best = Some(candidate);
+            }
+        }
+
+        s = e + 1;
+    }
+
+    best.unwrap()
+}
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but the rightmost range wins on
+/// ties, the same one as [`TieBreak::Rightmost`] in a single pass.
+///
+/// The scan replaces the best range with `>=`, so of equal products the later one wins,
+/// within a run that is the one with the most trailing ones. The leading ones are then
+/// trimmed to get the largest start. If no range has a positive product `(0, 0)` is
+/// returned, like `max_prod_fast_int` does.
+///
+/// ```
+/// use max_prod::{max_prod_fast_int, max_prod_fast_int_rightmost};
+///
+/// assert_eq!(max_prod_fast_int([2u32, 0, 1, 2, 1]), (0, 0));
+/// assert_eq!(max_prod_fast_int_rightmost([2u32, 0, 1, 2, 1]), (3, 4));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_rightmost<T: MaxProdInt>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let arr = arr.as_ref();
+    let mut best = None;
+
+    let mut start = 0;
+    let mut current_prod = T::zero();
+
+    for (i, &x) in arr.iter().enumerate() {
+        if x == T::zero() {
+            current_prod = T::zero();
+            continue;
+        }
+
+        if current_prod == T::zero() {
+            current_prod = T::one();
+            start = i;
+        }
+        current_prod = current_prod * x;
+
+        // not `consider`, the later range wins a tie
+        if best.is_none_or(|(p, _, _)| current_prod >= p) {
+            best = Some((current_prod, start, i));
+        }
+    }
+
+    let (_, i, j) = best_or_default(best).unwrap();
+    let mut max = (i, j);
+    while max.0 < max.1 && arr[max.0] == T::one() {
+        max.0 += 1;
+    }
+
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::max_prod_fast_int;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn max_prod_brute_force_with_tiebreak(arr: &[u64], tie_break: TieBreak) -> (usize, usize) {
+        let n = arr.len();
+        let mut ties = Vec::new();
+        let mut max_prod = 0;
+
+        for i in 0..n {
+            for j in i..n {
+                let p = prod(arr, i, j);
+
+                if p > max_prod {
+                    max_prod = p;
+                    ties.clear();
+                }
+                if p == max_prod && p > 0 {
+                    ties.push((i, j));
+                }
+            }
+        }
+
+        if ties.is_empty() {
+            return (0, 0);
+        }
+
+        // ties are sorted by start and then end
+        match tie_break {
+            TieBreak::FirstFound | TieBreak::Leftmost => ties[0],
+            TieBreak::Shortest => *ties.iter().min_by_key(|&&(i, j)| (j - i, i)).unwrap(),
+            TieBreak::Longest => *ties.iter().min_by_key(|&&(i, j)| (n - (j - i), i)).unwrap(),
+            TieBreak::Rightmost => *ties.iter().max_by_key(|&&(i, j)| (j, i)).unwrap(),
+        }
+    }
+
+    const ALL: [TieBreak; 5] = [TieBreak::FirstFound, TieBreak::Shortest, TieBreak::Longest, TieBreak::Leftmost, TieBreak::Rightmost];
+
+    #[test]
+    fn test_tiebreak_default() {
+        assert_eq!(TieBreak::default(), TieBreak::FirstFound);
+
+        let a = vec![0u32, 1, 0, 7, 0, 3, 1, 1, 7];
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::default()), max_prod_fast_int(&a));
+    }
+
+    #[test]
+    fn test_tiebreak_ones() {
+        let a = vec![1u32, 1, 1];
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::FirstFound), (0, 0));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Shortest), (0, 0));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Longest), (0, 2));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Leftmost), (0, 0));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Rightmost), (2, 2));
+    }
+
+    #[test]
+    fn test_tiebreak_runs() {
+        let a = vec![2u32, 0, 1, 2, 1, 0, 2];
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::FirstFound), (0, 0));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Shortest), (0, 0));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Longest), (2, 4));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Leftmost), (0, 0));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Rightmost), (6, 6));
+
+        let a = vec![1u32, 2, 1, 0, 2];
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::FirstFound), (0, 1));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Shortest), (1, 1));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Longest), (0, 2));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Leftmost), (0, 1));
+        assert_eq!(max_prod_fast_int_with_tiebreak(&a, TieBreak::Rightmost), (4, 4));
+    }
+
+    #[test]
+    fn test_tiebreak_zeros() {
+        let a = vec![0u32, 0, 0];
+        for tie_break in ALL {
+            assert_eq!(max_prod_fast_int_with_tiebreak(&a, tie_break), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_rightmost() {
+        // the whole range has a product of four, nothing ties
+        assert_eq!(max_prod_fast_int_rightmost([2u32, 1, 2]), (0, 2));
+        assert_eq!(max_prod_fast_int([2u32, 1, 2]), (0, 2));
+
+        // both twos tie
+        assert_eq!(max_prod_fast_int_rightmost([2u32, 0, 2]), (2, 2));
+        assert_eq!(max_prod_fast_int([2u32, 0, 2]), (0, 0));
+
+        assert_eq!(max_prod_fast_int_rightmost([1u32, 1, 1]), (2, 2));
+        assert_eq!(max_prod_fast_int_rightmost([0u32, 0]), (0, 0));
+        assert_eq!(max_prod_fast_int_rightmost::<u32>([]), (0, 0));
+    }
+
+    #[test]
+    fn test_random_rightmost() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..12);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 2).sample_iter(&mut rng).take(n).collect();
+
+            assert_eq!(max_prod_fast_int_rightmost(&a), max_prod_brute_force_with_tiebreak(&a, TieBreak::Rightmost), "a = {:?}", a);
+            assert_eq!(max_prod_fast_int_rightmost(&a), max_prod_fast_int_with_tiebreak(&a, TieBreak::Rightmost), "a = {:?}", a);
+        }
+    }
+
+    #[test]
+    fn test_random_tiebreak() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..12);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 2).sample_iter(&mut rng).take(n).collect();
+
+            for tie_break in ALL {
+                assert_eq!(max_prod_fast_int_with_tiebreak(&a, tie_break), max_prod_brute_force_with_tiebreak(&a, tie_break), "a = {:?} {:?}", a, tie_break);
+            }
+        }
+    }
+}
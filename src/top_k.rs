@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+
+
+use crate::int::MaxProdInt;
+use crate::max_prod::max_prod_fast_int_with_value;
+
+/// Greedily extracts up to `k` disjoint ranges, sorted by descending product.
+///
+/// After each pick the range is masked out with zeros and the search is repeated.
+/// Stops early once no range with a positive product is left, so fewer than `k`
+/// ranges may be returned.
+///
+/// ```
+/// use max_prod::top_k_max_prod_fast_int;
+///
+/// assert_eq!(top_k_max_prod_fast_int(&[2u32, 0, 3, 4, 0, 5], 2), vec![(2, 3), (5, 5)]);
+/// assert_eq!(top_k_max_prod_fast_int(&[2u32, 0, 3], 5), vec![(2, 2), (0, 0)]);
+/// ```
+#[must_use]
+pub fn top_k_max_prod_fast_int<T: MaxProdInt>(arr: &[T], k: usize) -> Vec<(usize, usize)> {
+    let mut masked = arr.to_vec();
+    let mut top = Vec::with_capacity(k);
+
+    while top.len() < k {
+        let (p, i, j) = max_prod_fast_int_with_value(&masked);
+
+        if p == T::zero() {
+            break;
+        }
+
+        top.push((i, j));
+        for x in &mut masked[i..=j] {
+            *x = T::zero();
+        }
+    }
+
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use num::traits::Num;
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::{max_prod_brute_force_with_value, prod};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    fn top_k_max_prod_brute_force<T: Num + Copy + PartialOrd>(arr: &[T], k: usize) -> Vec<(usize, usize)> {
+        let mut masked = arr.to_vec();
+        let mut top = Vec::new();
+
+        while top.len() < k {
+            let (p, i, j) = max_prod_brute_force_with_value(&masked);
+
+            if p == T::zero() {
+                break;
+            }
+
+            top.push((i, j));
+            for x in &mut masked[i..=j] {
+                *x = T::zero();
+            }
+        }
+
+        top
+    }
+
+    #[test]
+    fn test_top_k_basic() {
+        let a = vec![2u32, 3, 0, 7, 0, 1, 5];
+        assert_eq!(top_k_max_prod_fast_int(&a, 2), vec![(3, 3), (0, 1)]);
+        assert_eq!(top_k_max_prod_fast_int(&a, 10), vec![(3, 3), (0, 1), (5, 6)]);
+        assert_eq!(top_k_max_prod_fast_int(&a, 0), vec![]);
+    }
+
+    #[test]
+    fn test_top_k_fewer_runs() {
+        let a = vec![0u32, 0, 4, 0];
+        assert_eq!(top_k_max_prod_fast_int(&a, 3), vec![(2, 2)]);
+
+        let a = vec![0u32, 0];
+        assert_eq!(top_k_max_prod_fast_int(&a, 3), vec![]);
+    }
+
+    #[test]
+    fn test_random_top_k() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(12).collect();
+            let k = rng.gen_range(0..6);
+
+            let top = top_k_max_prod_fast_int(&a, k);
+            assert_eq!(top, top_k_max_prod_brute_force(&a, k));
+
+            for w in top.windows(2) {
+                assert!(prod(&a, w[0].0, w[0].1) >= prod(&a, w[1].0, w[1].1));
+            }
+        }
+    }
+}
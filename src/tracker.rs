@@ -0,0 +1,145 @@
+use crate::int::MaxProdInt;
+
+/// Streaming version of [`max_prod_fast_int`](crate::max_prod_fast_int).
+///
+/// Values are fed one at a time with [`push`](Self::push), feeding all elements of an
+/// array and then calling [`best`](Self::best) gives the same result as calling
+/// `max_prod_fast_int` on it.
+///
+/// ```
+/// use max_prod::MaxProdIntTracker;
+///
+/// let mut t = MaxProdIntTracker::new();
+/// for x in [2u32, 0, 3, 4] {
+///     t.push(x);
+/// }
+/// assert_eq!(t.best(), (2, 3));
+/// assert_eq!(t.best_prod(), 12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxProdIntTracker<T> {
+    max_prod: T,
+    max: (usize, usize),
+    current: (usize, usize),
+    current_prod: T,
+    n: usize,
+}
+
+impl<T: MaxProdInt> MaxProdIntTracker<T> {
+    pub fn new() -> Self {
+        MaxProdIntTracker {
+            max_prod: T::zero(),
+            max: (0, 0),
+            current: (0, 0),
+            current_prod: T::zero(),
+            n: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        let i = self.n;
+        self.n += 1;
+
+        if value != T::zero() {
+            if self.current_prod == T::zero() {
+                self.current_prod = T::one();
+                self.current.0 = i;
+            }
+            self.current_prod = self.current_prod * value;
+            self.current.1 = i;
+        } else {
+            self.current = (i, i);
+            self.current_prod = T::zero();
+        }
+
+        if self.current_prod > self.max_prod {
+            self.max = self.current;
+            self.max_prod = self.current_prod;
+        }
+    }
+
+    /// The best range of all values pushed so far.
+    pub fn best(&self) -> (usize, usize) {
+        self.max
+    }
+
+    /// The product of [`best`](Self::best).
+    pub fn best_prod(&self) -> T {
+        self.max_prod
+    }
+
+    /// Number of values pushed so far.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+impl<T: MaxProdInt> Default for MaxProdIntTracker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: MaxProdInt> Extend<T> for MaxProdIntTracker<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::{max_prod_fast_int, max_prod_fast_int_with_value};
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_tracker_basic() {
+        let mut t = MaxProdIntTracker::new();
+        assert!(t.is_empty());
+        assert_eq!(t.best(), (0, 0));
+
+        t.push(0u32);
+        assert_eq!(t.best(), (0, 0));
+        t.push(2);
+        assert_eq!(t.best(), (1, 1));
+        t.push(3);
+        assert_eq!(t.best(), (1, 2));
+        t.push(0);
+        t.push(5);
+        assert_eq!(t.best(), (1, 2));
+        t.push(2);
+        assert_eq!(t.best(), (4, 5));
+        assert_eq!(t.best_prod(), 10);
+        assert_eq!(t.len(), 6);
+    }
+
+    #[test]
+    fn test_random_tracker() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let a: Vec<u128> = Uniform::new_inclusive(0, 5).sample_iter(&mut rng).take(50).collect();
+            let mut t = MaxProdIntTracker::new();
+
+            for (k, &x) in a.iter().enumerate() {
+                t.push(x);
+                assert_eq!(t.best(), max_prod_fast_int(&a[..=k]));
+            }
+
+            assert_eq!((t.best_prod(), t.best().0, t.best().1), max_prod_fast_int_with_value(&a));
+
+            let mut t = MaxProdIntTracker::default();
+            t.extend(a.iter().copied());
+            assert_eq!(t.best(), max_prod_fast_int(&a));
+        }
+    }
+}
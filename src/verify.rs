@@ -0,0 +1,77 @@
+use num::traits::Num;
+
+use crate::max_prod::{max_prod_brute_force_improved_with_value, prod};
+
+/// Checks a result claimed by some algorithm against
+/// [`max_prod_brute_force_improved`](crate::max_prod_brute_force_improved), O(n²).
+///
+/// Returns whether `(i, j)` is a range of `arr` and no other range has a strictly larger
+/// product. Which of several ranges with the maximum product is claimed doesn't matter.
+/// Like in the algorithms, `(0, 0)` is accepted for an empty array and for an array in which
+/// no range has a positive product.
+///
+/// ```
+/// use max_prod::{max_prod_fast_real, verify_max_prod};
+///
+/// let a = [0.5, 2.0, 0.5, 3.0];
+/// assert!(verify_max_prod(&a, max_prod_fast_real(&a)));
+/// assert!(!verify_max_prod(&a, (1, 1)));
+/// ```
+#[must_use]
+pub fn verify_max_prod<T: Num + Copy + PartialOrd>(arr: impl AsRef<[T]>, (i, j): (usize, usize)) -> bool {
+    let arr = arr.as_ref();
+    let (max_prod, k, l) = max_prod_brute_force_improved_with_value(arr);
+
+    if (i, j) == (k, l) {
+        return true;
+    }
+
+    i <= j && j < arr.len() && prod(arr, i, j) >= max_prod
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::max_prod_fast_int;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_wrong() {
+        let a = [2u32, 0, 3, 4, 1, 0, 5];
+
+        assert!(verify_max_prod(a, (2, 3)));
+        assert!(verify_max_prod(a, (2, 4))); // a tie
+        assert!(!verify_max_prod(a, (2, 2)));
+        assert!(!verify_max_prod(a, (6, 6)));
+        assert!(!verify_max_prod(a, (0, 3)));
+
+        // not a range of the array
+        assert!(!verify_max_prod(a, (3, 2)));
+        assert!(!verify_max_prod(a, (2, 7)));
+
+        assert!(verify_max_prod::<u32>([], (0, 0)));
+        assert!(!verify_max_prod::<u32>([], (0, 1)));
+        assert!(verify_max_prod([-3i32, 0], (0, 0)));
+        assert!(verify_max_prod([-3i32, 0], (1, 1)));
+    }
+
+    #[test]
+    fn test_random_verify() {
+        let mut rng = test_rng();
+        for _ in 0..300 {
+            let n = rng.gen_range(1..20);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(n).collect();
+            let (k, l) = max_prod_fast_int(&a);
+            assert!(verify_max_prod(&a, (k, l)), "{a:?}");
+
+            let i = rng.gen_range(0..n);
+            let j = rng.gen_range(i..n);
+            assert_eq!(verify_max_prod(&a, (i, j)), prod(&a, i, j) == prod(&a, k, l), "{a:?}");
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::real::max_prod_fast_real;
+
+/// [`max_prod_fast_real`] for JavaScript, returns `[i, j]`.
+///
+/// ```
+/// use max_prod::max_prod_fast_real_js;
+///
+/// assert_eq!(max_prod_fast_real_js(&[0.5, 2.0, 3.0, 0.1]), vec![1, 2]);
+/// ```
+#[wasm_bindgen]
+pub fn max_prod_fast_real_js(arr: &[f64]) -> Vec<usize> {
+    let (i, j) = max_prod_fast_real(arr);
+    vec![i, j]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_js() {
+        assert_eq!(max_prod_fast_real_js(&[0.5, 4.0, 0.5, 3.0, 0.1]), vec![1, 3]);
+        assert_eq!(max_prod_fast_real_js(&[]), vec![0, 0]);
+    }
+}
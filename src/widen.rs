@@ -0,0 +1,104 @@
+use crate::int::MaxProdInt;
+use crate::tracker::MaxProdIntTracker;
+
+/// Unsigned integers with a wider type to accumulate products in.
+///
+/// | `Self` | `Wide` |
+/// |--------|--------|
+/// | `u8`   | `u32`  |
+/// | `u16`  | `u64`  |
+/// | `u32`  | `u128` |
+/// | `u64`  | `u128` |
+/// | `usize`| `u128` |
+pub trait Widen: Copy {
+    type Wide: MaxProdInt;
+
+    fn widen(self) -> Self::Wide;
+}
+
+macro_rules! impl_widen {
+    ($($t:ty => $w:ty),*) => {
+        $(
+            impl Widen for $t {
+                type Wide = $w;
+
+                fn widen(self) -> $w {
+                    self as $w
+                }
+            }
+        )*
+    };
+}
+
+impl_widen!(u8 => u32, u16 => u64, u32 => u128, u64 => u128, usize => u128);
+
+/// Like [`max_prod_fast_int`](crate::max_prod_fast_int) but multiplies in [`Widen::Wide`],
+/// so e.g. `u8` arrays whose maximum product is larger than 255 still work.
+///
+/// Widening only moves the point where the product overflows, `u8` values multiplied
+/// in `u32` can still overflow once a range has more than four elements.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_widening;
+///
+/// assert_eq!(max_prod_fast_int_widening([200u8, 0, 100, 3]), (2, 3));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_widening<T: Widen>(arr: impl AsRef<[T]>) -> (usize, usize) {
+    let (_, i, j) = max_prod_fast_int_widening_with_value(arr);
+    (i, j)
+}
+
+/// Like [`max_prod_fast_int_widening`] but also returns the widened product of the winning range.
+///
+/// ```
+/// use max_prod::max_prod_fast_int_widening_with_value;
+///
+/// // 200 * 200 doesn't fit into a u8
+/// assert_eq!(max_prod_fast_int_widening_with_value([200u8, 200, 0, 3]), (40000u32, 0, 1));
+/// ```
+#[must_use]
+pub fn max_prod_fast_int_widening_with_value<T: Widen>(arr: impl AsRef<[T]>) -> (T::Wide, usize, usize) {
+    let mut tracker = MaxProdIntTracker::new();
+    tracker.extend(arr.as_ref().iter().map(|x| x.widen()));
+
+    let (i, j) = tracker.best();
+    (tracker.best_prod(), i, j)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::max_prod_fast_int;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_widening_u8() {
+        assert_eq!(max_prod_fast_int_widening_with_value([16u8, 16]), (256, 0, 1));
+        assert_eq!(max_prod_fast_int_widening_with_value([255u8, 255, 0, 2]), (65025, 0, 1));
+        assert_eq!(max_prod_fast_int_widening_with_value([200u8, 0, 100, 3]), (300, 2, 3));
+        assert_eq!(max_prod_fast_int_widening_with_value([0u8, 0]), (0, 0, 0));
+        assert_eq!(max_prod_fast_int_widening_with_value::<u8>([]), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_widening_u16_usize() {
+        assert_eq!(max_prod_fast_int_widening_with_value([300u16, 300, 0, 7]), (90000, 0, 1));
+        assert_eq!(max_prod_fast_int_widening([usize::MAX, 2]), (0, 1));
+    }
+
+    #[test]
+    fn test_random_widening_u8() {
+        let mut rng = test_rng();
+
+        for _ in 0..200 {
+            let a: Vec<u8> = Uniform::new_inclusive(0, 3).sample_iter(&mut rng).take(12).collect();
+            let wide: Vec<u32> = a.iter().map(|&x| x as u32).collect();
+            assert_eq!(max_prod_fast_int_widening(&a), max_prod_fast_int(&wide));
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use num::traits::Num;
+
+use crate::int::MaxProdInt;
+
+/// The products of all windows of `w` consecutive elements, from left to right.
+///
+/// Each product is computed from the previous one by multiplying in the new element and
+/// dividing out the one leaving the window. Like in [`PrefixProduct`](crate::PrefixProduct)
+/// zeros are only counted instead of multiplied in, so a zero leaving the window doesn't
+/// need the window to be recomputed. The product of the non-zero elements of a window has
+/// to fit into `T`, for floats every division can add a small rounding error. Yields nothing
+/// if `w` is zero or larger than `arr`.
+///
+/// ```
+/// use max_prod::window_products;
+///
+/// let products: Vec<u32> = window_products(&[2, 3, 0, 4, 5], 2).collect();
+/// assert_eq!(products, vec![6, 0, 0, 20]);
+/// ```
+pub fn window_products<T: Num + Copy>(arr: &[T], w: usize) -> impl Iterator<Item = T> + '_ {
+    WindowProducts { arr, w, next: 0, prod: T::one(), zeros: 0 }
+}
+
+/// The window of exactly `w` elements with the largest product, `None` if `w` is zero or
+/// larger than `arr`.
+///
+/// The products come from [`window_products`], so the product of the non-zero elements of
+/// every window has to fit into `T`. On ties the leftmost window wins, if every window
+/// contains a zero that is `(0, w - 1)`.
+///
+/// ```
+/// use max_prod::max_prod_fixed_len_int;
+///
+/// assert_eq!(max_prod_fixed_len_int([2u32, 3, 0, 4, 5, 1], 2), Some((3, 4)));
+/// assert_eq!(max_prod_fixed_len_int([2u32, 3, 0, 4, 5, 1], 3), Some((3, 5)));
+/// assert_eq!(max_prod_fixed_len_int([2u32, 3], 3), None);
+/// ```
+#[must_use]
+pub fn max_prod_fixed_len_int<T: MaxProdInt>(arr: impl AsRef<[T]>, w: usize) -> Option<(usize, usize)> {
+    let mut best: Option<(T, usize)> = None;
+
+    for (i, p) in window_products(arr.as_ref(), w).enumerate() {
+        if best.is_none_or(|(max, _)| p > max) {
+            best = Some((p, i));
+        }
+    }
+
+    best.map(|(_, i)| (i, i + w - 1))
+}
+
+struct WindowProducts<'a, T> {
+    arr: &'a [T],
+    w: usize,
+    // index of the element the next window ends with
+    next: usize,
+    // product of the non-zero elements of the current window
+    prod: T,
+    // number of zeros in the current window
+    zeros: usize,
+}
+
+impl<T: Num + Copy> WindowProducts<'_, T> {
+    fn push(&mut self, x: T) {
+        if x == T::zero() {
+            self.zeros += 1;
+        } else {
+            self.prod = self.prod * x;
+        }
+    }
+
+    fn pop(&mut self, x: T) {
+        if x == T::zero() {
+            self.zeros -= 1;
+        } else {
+            self.prod = self.prod / x;
+        }
+    }
+}
+
+impl<T: Num + Copy> Iterator for WindowProducts<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.w == 0 || self.w > self.arr.len() || self.next >= self.arr.len() {
+            return None;
+        }
+
+        if self.next == 0 {
+            for k in 0..self.w {
+                self.push(self.arr[k]);
+            }
+            self.next = self.w - 1;
+        } else {
+            // out first, so the product never covers more than one window
+            self.pop(self.arr[self.next - self.w]);
+            self.push(self.arr[self.next]);
+        }
+        self.next += 1;
+
+        Some(if self.zeros > 0 { T::zero() } else { self.prod })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand::distributions::uniform::Uniform;
+    use rand::distributions::Distribution;
+
+    use crate::max_prod::prod;
+    use crate::test_rng::test_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_window_basic() {
+        let arr = [1u32, 2, 3, 4];
+        assert_eq!(window_products(&arr, 1).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(window_products(&arr, 3).collect::<Vec<_>>(), vec![6, 24]);
+        assert_eq!(window_products(&arr, 4).collect::<Vec<_>>(), vec![24]);
+        assert_eq!(window_products(&arr, 5).count(), 0);
+        assert_eq!(window_products(&arr, 0).count(), 0);
+        assert_eq!(window_products::<u32>(&[], 1).count(), 0);
+    }
+
+    #[test]
+    fn test_window_zeros() {
+        let arr = [0i32, 2, 0, 0, -3, 4, 0];
+        assert_eq!(window_products(&arr, 2).collect::<Vec<_>>(), vec![0, 0, 0, 0, -12, 0]);
+        assert_eq!(window_products(&arr, 1).collect::<Vec<_>>(), arr.to_vec());
+    }
+
+    #[test]
+    fn test_window_no_overflow() {
+        // 16 * 16 and 200 * 2 don't fit into a u8, but no window contains both
+        assert_eq!(window_products(&[16u8, 16], 1).collect::<Vec<_>>(), vec![16, 16]);
+        assert_eq!(window_products(&[15u8, 17, 15], 2).collect::<Vec<_>>(), vec![255, 255]);
+        assert_eq!(max_prod_fixed_len_int([200u8, 2], 1), Some((0, 0)));
+        assert_eq!(max_prod_fixed_len_int([2u8, 0, 200, 3, 85], 1), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_random_window() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(1..30);
+            let w = rng.gen_range(1..=n);
+            let a: Vec<i64> = Uniform::new_inclusive(-3, 3).sample_iter(&mut rng).take(n).collect();
+
+            let naive: Vec<i64> = (0..=n - w).map(|i| prod(&a, i, i + w - 1)).collect();
+            assert_eq!(window_products(&a, w).collect::<Vec<_>>(), naive, "{a:?} {w}");
+        }
+    }
+
+    #[test]
+    fn test_fixed_len() {
+        let arr = [0u32, 0, 3, 0, 0];
+        assert_eq!(max_prod_fixed_len_int(arr, 1), Some((2, 2)));
+        assert_eq!(max_prod_fixed_len_int(arr, 2), Some((0, 1)));
+        assert_eq!(max_prod_fixed_len_int(arr, 5), Some((0, 4)));
+        assert_eq!(max_prod_fixed_len_int(arr, 6), None);
+        assert_eq!(max_prod_fixed_len_int(arr, 0), None);
+        assert_eq!(max_prod_fixed_len_int::<u32>([], 1), None);
+
+        // both windows have a product of six
+        assert_eq!(max_prod_fixed_len_int([2u32, 3, 1, 6], 2), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_random_fixed_len() {
+        let mut rng = test_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(1..30);
+            let w = rng.gen_range(1..=n);
+            let a: Vec<u64> = Uniform::new_inclusive(0, 4).sample_iter(&mut rng).take(n).collect();
+
+            // every window of length `w`, the first of the largest wins
+            let mut expected = (0, w - 1);
+            for i in 1..=n - w {
+                if prod(&a, i, i + w - 1) > prod(&a, expected.0, expected.1) {
+                    expected = (i, i + w - 1);
+                }
+            }
+
+            assert_eq!(max_prod_fixed_len_int(&a, w), Some(expected), "{a:?} {w}");
+        }
+    }
+
+    #[test]
+    fn test_random_window_real() {
+        let mut rng = test_rng();
+        for _ in 0..200 {
+            let a: Vec<f64> = Uniform::new(0.5, 2.0).sample_iter(&mut rng).take(50).collect();
+
+            for (i, p) in window_products(&a, 5).enumerate() {
+                let q = prod(&a, i, i + 4);
+                assert!((p - q).abs() <= q * 1e-9, "{a:?} {i}");
+            }
+        }
+    }
+}
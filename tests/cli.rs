@@ -0,0 +1,98 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], input: &str) -> Output {
+    run_bytes(args, input.as_bytes())
+}
+
+fn run_bytes(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_max-prod"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // invalid arguments make it exit without reading its input, which breaks the pipe
+    let _ = child.stdin.take().unwrap().write_all(input);
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(args: &[&str], input: &str) -> String {
+    let out = run(args, input);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    String::from_utf8(out.stdout).unwrap()
+}
+
+#[test]
+fn test_int() {
+    assert_eq!(stdout(&[], "2 0 3 4\n0 5\n"), "[2 .. 3] = 12\n");
+    assert_eq!(stdout(&["--algo", "brute"], "2 0 3 4\n0 5\n"), "[2 .. 3] = 12\n");
+    assert_eq!(stdout(&["--algo", "fast-real"], "2 0 3 4\n0 5\n"), "[2 .. 3] = 12\n");
+}
+
+#[test]
+fn test_real() {
+    assert_eq!(stdout(&[], "0.5 4\n0.5 3 0.1"), "[1 .. 3] = 6\n");
+    assert_eq!(stdout(&["--algo", "brute"], "0.5 4 0.5 3 0.1"), "[1 .. 3] = 6\n");
+    assert_eq!(stdout(&[], "-2 0.5 -3 0 2"), "[0 .. 2] = 3\n");
+}
+
+#[test]
+fn test_csv() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/factors.csv");
+
+    let out = run(&["--csv", path, "--column", "1"], "");
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "[1 .. 3] = 6\nrows 2 .. 5\n");
+
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("skipping row 0"));
+    assert!(stderr.contains("skipping row 3"));
+
+    assert_eq!(stdout(&["--csv", path, "--column", "2"], ""), "[2 .. 5] = 120\nrows 3 .. 6\n");
+    assert!(!run(&["--csv", "does/not/exist.csv"], "").status.success());
+    assert!(!run(&["--csv", path, "--column", "7"], "").status.success());
+}
+
+#[test]
+fn test_binary() {
+    let bytes: Vec<u8> = [2u64, 0, 3, 4, 0, 5].iter().flat_map(|x| x.to_le_bytes()).collect();
+
+    let out = run_bytes(&["--binary"], &bytes);
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "[2 .. 3] = 12\n");
+
+    let out = run_bytes(&["--binary", "--algo", "brute"], &bytes);
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "[2 .. 3] = 12\n");
+
+    // the last number is cut off
+    let out = run_bytes(&["--binary"], &bytes[..bytes.len() - 3]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8(out.stderr).unwrap().contains("truncated"));
+
+    assert!(!run_bytes(&["--binary"], &[]).status.success());
+    assert!(!run(&["--binary", "--csv", "x.csv"], "").status.success());
+}
+
+#[test]
+fn test_errors() {
+    assert!(!run(&[], "").status.success());
+    assert!(!run(&[], "1 2 abc").status.success());
+    assert!(!run(&["--algo", "fast-int"], "0.5 2").status.success());
+    assert!(!run(&["--algo", "nope"], "1 2").status.success());
+}
+
+#[test]
+fn test_overflow() {
+    // (2^32 + 1)^2 doesn't fit into a u64
+    for args in [&[][..], &["--algo", "brute"]] {
+        let out = run(args, "4294967297 4294967297");
+        assert!(out.status.success());
+        assert_eq!(String::from_utf8(out.stdout).unwrap(), "[0 .. 1] = overflow\n");
+        assert!(String::from_utf8(out.stderr).unwrap().contains("warning: the product of [0 .. 1] overflows u64"));
+    }
+
+    assert_eq!(stdout(&[], "4294967295 0 1"), "[0 .. 0] = 4294967295\n");
+}